@@ -1,16 +1,28 @@
 use std::path::PathBuf;
 use rust_decimal_macros::dec;
 use rust_decimal::Decimal;
-use sky130pdk::Sky130Pdk; 
-use sky130pdk::atoll::{MosTile, TapTile};
-use ucieanalog::strongarm::tb::{StrongArmTranTb, ComparatorDecision};
-use ucieanalog::strongarm::{StrongArmImpl, InputKind, StrongArmParams, StrongArm}; 
-use ucieanalog::tiles::{MosKind, MosTileParams, TapIo, TapTileParams, TileKind}; 
-use atoll::{TileBuilder, TileWrapper}; 
-use substrate::pdk::corner::Pvt;  
+use serde::{Deserialize, Serialize};
+use sky130pdk::Sky130Pdk;
+use sky130pdk::atoll::{MosTile, MosTileIoSchematic, TapTile};
+use ucieanalog::buffer::{InverterImpl, InverterParams};
+use ucieanalog::strongarm::tb::{StrongArmTranTb, ComparatorDecision, SimBackend, Dut, ExtractedDut};
+use ucieanalog::strongarm::router::LeeMazeRouter;
+use ucieanalog::strongarm::{
+    BufferChainParams, Nand2Io, NandImpl, SrLatchImpl, StrongArmImpl, StrongArmWithOutputBuffers,
+    StrongArmWithOutputBuffersImpl, StrongArmWithSrLatch, TopTileImpl, InputKind, StrongArmParams,
+    StrongArm, Matching,
+};
+use ucieanalog::tiles::{MosKind, MosTileParams, TapIo, TapTileParams, TileKind};
+use atoll::{IoBuilder, Tile, TileBuilder, TileWrapper};
+use substrate::block::Block;
+use substrate::geometry::align::AlignMode;
+use substrate::io::{Array, Signal};
+use substrate::layout::ExportsLayoutData;
+use substrate::pdk::corner::Pvt;
 use sky130pdk::corner::Sky130Corner;
-use substrate::context::{Context, PdkContext}; 
-use ngspice::Ngspice;  
+use substrate::context::{Context, PdkContext};
+use substrate::schematic::ExportsNestedData;
+use ngspice::Ngspice;
 use substrate::schematic::netlist::ConvertibleNetlister;
 use spice::Spice;
 use spectre::Spectre;
@@ -22,6 +34,7 @@ impl StrongArmImpl<Sky130Pdk> for Sky130strongarm {
     type MosTile = MosTile;
     type TapTile = TapTile;
     type ViaMaker = sky130pdk::atoll::Sky130ViaMaker;
+    type Router = atoll::route::GreedyRouter;
 
     fn mos(params: MosTileParams) -> Self::MosTile {
         MosTile::new(6, 0.15, params.mos_kind)
@@ -32,26 +45,196 @@ impl StrongArmImpl<Sky130Pdk> for Sky130strongarm {
     fn via_maker() -> Self::ViaMaker {
         sky130pdk::atoll::Sky130ViaMaker
     }
+    fn router() -> Self::Router {
+        atoll::route::GreedyRouter::new()
+    }
     fn post_layout_hooks(cell: &mut TileBuilder<'_, Sky130Pdk>) -> substrate::error::Result<()> {
         Ok(())
     }
 }
 
+impl InverterImpl<Sky130Pdk> for Sky130strongarm {
+    type MosTile = MosTile;
+    type ViaMaker = sky130pdk::atoll::Sky130ViaMaker;
+
+    fn mos(params: MosTileParams) -> Self::MosTile {
+        MosTile::new(6, 0.15, params.mos_kind)
+    }
+    fn via_maker() -> Self::ViaMaker {
+        sky130pdk::atoll::Sky130ViaMaker
+    }
+}
+
+impl TopTileImpl<Sky130Pdk> for Sky130strongarm {
+    const TOP_LAYER: i64 = 2;
+    type Router = LeeMazeRouter;
+
+    fn router() -> Self::Router {
+        LeeMazeRouter::new()
+    }
+}
+
+impl StrongArmWithOutputBuffersImpl<Sky130Pdk> for Sky130strongarm {
+    const BUFFER_SPACING: i64 = 2;
+}
+
+/// A minimal two-input NAND2 gate: two parallel PMOS pull-up devices
+/// (sources on `vdd`, drains on `y`) and two series NMOS pull-down devices
+/// (stacked between `y` and `vss`), the classic NAND2 stick diagram.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct Sky130Nand2;
+
+impl Block for Sky130Nand2 {
+    type Io = Nand2Io;
+
+    fn id() -> substrate::arcstr::ArcStr {
+        substrate::arcstr::literal!("sky130_nand2")
+    }
+
+    fn name(&self) -> substrate::arcstr::ArcStr {
+        substrate::arcstr::literal!("sky130_nand2")
+    }
+
+    fn io(&self) -> Self::Io {
+        Default::default()
+    }
+}
+
+impl ExportsNestedData for Sky130Nand2 {
+    type NestedData = ();
+}
+
+impl ExportsLayoutData for Sky130Nand2 {
+    type LayoutData = ();
+}
+
+impl Tile<Sky130Pdk> for Sky130Nand2 {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, Sky130Pdk>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let pull_up_params = MosTileParams::new(MosKind::Lvt, TileKind::P, 2);
+        let pull_down_params = MosTileParams::new(MosKind::Lvt, TileKind::N, 2);
+        let mos = |params: MosTileParams| <Sky130strongarm as StrongArmImpl<Sky130Pdk>>::mos(params);
+
+        let mut pmos_a = cell.generate_connected(
+            mos(pull_up_params),
+            MosTileIoSchematic {
+                sd: cell.signal("pmos_a_sd", Array::new(2, Signal)),
+                g: cell.signal("pmos_a_g", Array::new(1, Signal)),
+                b: io.schematic.vdd,
+            },
+        );
+        cell.connect(pmos_a.io().sd[0], io.schematic.vdd);
+        cell.connect(pmos_a.io().sd[1], io.schematic.y);
+        cell.connect(pmos_a.io().g[0], io.schematic.a);
+
+        let mut pmos_b = cell
+            .generate_connected(
+                mos(pull_up_params),
+                MosTileIoSchematic {
+                    sd: cell.signal("pmos_b_sd", Array::new(2, Signal)),
+                    g: cell.signal("pmos_b_g", Array::new(1, Signal)),
+                    b: io.schematic.vdd,
+                },
+            )
+            .align(&pmos_a, AlignMode::ToTheRight, 0)
+            .align(&pmos_a, AlignMode::CenterVertical, 0);
+        cell.connect(pmos_b.io().sd[0], io.schematic.vdd);
+        cell.connect(pmos_b.io().sd[1], io.schematic.y);
+        cell.connect(pmos_b.io().g[0], io.schematic.b);
+
+        let mid = cell.signal("nand2_mid", Signal);
+        let mut nmos_a = cell
+            .generate_connected(
+                mos(pull_down_params),
+                MosTileIoSchematic {
+                    sd: cell.signal("nmos_a_sd", Array::new(2, Signal)),
+                    g: cell.signal("nmos_a_g", Array::new(1, Signal)),
+                    b: io.schematic.vss,
+                },
+            )
+            .align(&pmos_b, AlignMode::ToTheRight, 0)
+            .align(&pmos_b, AlignMode::CenterVertical, 0);
+        cell.connect(nmos_a.io().sd[0], io.schematic.y);
+        cell.connect(nmos_a.io().sd[1], mid);
+        cell.connect(nmos_a.io().g[0], io.schematic.a);
+
+        let mut nmos_b = cell
+            .generate_connected(
+                mos(pull_down_params),
+                MosTileIoSchematic {
+                    sd: cell.signal("nmos_b_sd", Array::new(2, Signal)),
+                    g: cell.signal("nmos_b_g", Array::new(1, Signal)),
+                    b: io.schematic.vss,
+                },
+            )
+            .align(&nmos_a, AlignMode::ToTheRight, 0)
+            .align(&nmos_a, AlignMode::CenterVertical, 0);
+        cell.connect(nmos_b.io().sd[0], mid);
+        cell.connect(nmos_b.io().sd[1], io.schematic.vss);
+        cell.connect(nmos_b.io().g[0], io.schematic.b);
+
+        cell.draw(pmos_a)?;
+        cell.draw(pmos_b)?;
+        cell.draw(nmos_a)?;
+        cell.draw(nmos_b)?;
+
+        cell.set_top_layer(1);
+        cell.set_router(atoll::route::GreedyRouter::new());
+        cell.set_via_maker(<Sky130strongarm as NandImpl<Sky130Pdk>>::nand2_via_maker());
+
+        Ok(((), ()))
+    }
+}
+
+impl NandImpl<Sky130Pdk> for Sky130strongarm {
+    type Nand2Tile = Sky130Nand2;
+    type Nand2ViaMaker = sky130pdk::atoll::Sky130ViaMaker;
+
+    fn nand2() -> Self::Nand2Tile {
+        Sky130Nand2
+    }
+    fn nand2_via_maker() -> Self::Nand2ViaMaker {
+        sky130pdk::atoll::Sky130ViaMaker
+    }
+}
+
+impl SrLatchImpl<Sky130Pdk> for Sky130strongarm {
+    const LATCH_SPACING: i64 = 2;
+}
+
+/// Builds a [`PdkContext`] for the open sky130 PDK with the default analog
+/// simulator backend ([`Spectre`]).
 pub fn sky130_open_ctx() -> PdkContext<Sky130Pdk> {
+    sky130_open_ctx_with(SimBackend::Spectre)
+}
+
+/// Builds a [`PdkContext`] for the open sky130 PDK, installing whichever
+/// analog simulator `backend` selects.
+///
+/// Both backends install into the context the same way. The
+/// [`StrongArmTranTb`] run in this context takes its own [`SimBackend`],
+/// which has to name the same simulator as `backend` here.
+pub fn sky130_open_ctx_with(backend: SimBackend) -> PdkContext<Sky130Pdk> {
     let pdk_root = std::env::var("SKY130_OPEN_PDK_ROOT")
         .expect("the SKY130_OPEN_PDK_ROOT environment variable must be set");
-    Context::builder()
-        .install(Spectre::default())
-        .install(Sky130Pdk::open(pdk_root))
-        .build()
-        .with_pdk()
+    let ctx = Context::builder();
+    let ctx = match backend {
+        SimBackend::Spectre => ctx.install(Spectre::default()),
+        SimBackend::Ngspice => ctx.install(Ngspice::default()),
+    };
+    ctx.install(Sky130Pdk::open(pdk_root)).build().with_pdk()
 }
 
 #[test] 
 fn strongarm_sim() {
     let work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/strongarm_sim");
-    let input_kind = InputKind::N; 
-    let pex_work_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/build/strongarm_sim/pex");
+    let input_kind = InputKind::N;
     let dut = TileWrapper::new(StrongArm::<Sky130strongarm>::new(StrongArmParams {
         nmos_kind: MosKind::Lvt,
         pmos_kind: MosKind::Lvt,
@@ -61,6 +244,10 @@ fn strongarm_sim() {
         inv_precharge_w: 2,
         precharge_w: 2,
         input_kind,
+        strap_width: 3,
+        strap_on_abutment_edges: true,
+        matching: Matching::None,
+        max_finger_w: 0,
     })); 
 
     let pvt = Pvt {
@@ -103,13 +290,12 @@ fn strongarm_sim() {
                 }
             }
             
-            let tb = StrongArmTranTb::new(dut.clone(), vinp, vinn, input_kind.is_n(), pvt); 
-            let decision = ctx
+            let tb = StrongArmTranTb::new(dut.clone(), vinp, vinn, input_kind.is_n(), pvt, SimBackend::Spectre);
+            let result = ctx
                 .simulate(tb, work_dir)
-                .expect("failed to run simulation")
-                .expect("comparator output did not rail"); 
+                .expect("failed to run simulation");
             assert_eq!(
-                decision, 
+                result.decision,
                 if j > dec!(0) {
                     ComparatorDecision::Pos
                 } else {
@@ -128,14 +314,8 @@ fn strongarm_lvs() {
     let work_dir = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/build/strongarm_lvs"));
     let gds_path = work_dir.join("layout.gds"); 
     let netlist_path = work_dir.join("netlist.sp");
-    let pdk_root = std::env::var("SKY130_OPEN_PDK_ROOT")
-        .expect("the SKY130_OPEN_PDK_ROOT environment variable must be set");
-    let ctx = Context::builder()
-    .install(Spectre::default())
-    .install(Sky130Pdk::open(pdk_root))
-    .build()
-    .with_pdk(); 
-  
+    let ctx = sky130_open_ctx_with(SimBackend::Spectre);
+
     let block = TileWrapper::new(StrongArm::<Sky130strongarm>::new(StrongArmParams {
         nmos_kind: MosKind::Lvt,
         pmos_kind: MosKind::Lvt,
@@ -145,6 +325,10 @@ fn strongarm_lvs() {
         inv_precharge_w: 2,
         precharge_w: 2,
         input_kind: InputKind::N,
+        strap_width: 3,
+        strap_on_abutment_edges: true,
+        matching: Matching::None,
+        max_finger_w: 0,
     }));
 
     let scir = ctx
@@ -158,7 +342,210 @@ fn strongarm_lvs() {
     Spice 
         .write_scir_netlist_to_file(&scir, netlist_path, NetlistOptions::default())
         .expect("failed to write netlist");
-    ctx.write_layout(block, gds_path) 
+    ctx.write_layout(block, gds_path)
+        .expect("failed to write layout");
+
+}
+
+/// Generates a layout and schematic netlist for a [`StrongArmWithSrLatch`]
+/// built out of [`Sky130strongarm`]'s gates, as a compile/layout smoke test
+/// for the [`NandImpl`]/[`SrLatchImpl`] impls above.
+#[test]
+fn strongarm_with_sr_latch_lvs() {
+    let work_dir =
+        PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/build/strongarm_with_sr_latch_lvs"));
+    let gds_path = work_dir.join("layout.gds");
+    let netlist_path = work_dir.join("netlist.sp");
+    let ctx = sky130_open_ctx_with(SimBackend::Spectre);
+
+    let block = TileWrapper::new(StrongArmWithSrLatch::<Sky130strongarm>::new(StrongArmParams {
+        nmos_kind: MosKind::Lvt,
+        pmos_kind: MosKind::Lvt,
+        half_tail_w: 2,
+        input_pair_w: 2,
+        inv_input_w: 2,
+        inv_precharge_w: 2,
+        precharge_w: 2,
+        input_kind: InputKind::N,
+        strap_width: 3,
+        strap_on_abutment_edges: true,
+        matching: Matching::None,
+        max_finger_w: 0,
+    }));
+
+    let scir = ctx
+        .export_scir(block)
+        .unwrap()
+        .scir
+        .convert_schema::<Spice>()
+        .unwrap()
+        .build()
+        .unwrap();
+    Spice
+        .write_scir_netlist_to_file(&scir, netlist_path, NetlistOptions::default())
+        .expect("failed to write netlist");
+    ctx.write_layout(block, gds_path)
         .expect("failed to write layout");
+}
+
+/// Generates a layout and schematic netlist for a tapered three-stage
+/// [`StrongArmWithOutputBuffers`] chain, as a compile/layout smoke test for
+/// the [`InverterImpl`] impl above.
+#[test]
+fn strongarm_with_output_buffers_lvs() {
+    let work_dir = PathBuf::from(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/build/strongarm_with_output_buffers_lvs"
+    ));
+    let gds_path = work_dir.join("layout.gds");
+    let netlist_path = work_dir.join("netlist.sp");
+    let ctx = sky130_open_ctx_with(SimBackend::Spectre);
+
+    let sa_params = StrongArmParams {
+        nmos_kind: MosKind::Lvt,
+        pmos_kind: MosKind::Lvt,
+        half_tail_w: 2,
+        input_pair_w: 2,
+        inv_input_w: 2,
+        inv_precharge_w: 2,
+        precharge_w: 2,
+        input_kind: InputKind::N,
+        strap_width: 3,
+        strap_on_abutment_edges: true,
+        matching: Matching::None,
+        max_finger_w: 0,
+    };
+    // Tapered chain: a light first stage so it doesn't load the latch's
+    // output nodes, stepping up to a heavier last stage that can drive an
+    // off-tile load.
+    let buf_params = BufferChainParams::new(vec![
+        InverterParams::new(2, 2),
+        InverterParams::new(4, 4),
+        InverterParams::new(8, 8),
+    ]);
+    let block = TileWrapper::new(StrongArmWithOutputBuffers::<Sky130strongarm>::new(
+        sa_params, buf_params,
+    ));
+
+    let scir = ctx
+        .export_scir(block.clone())
+        .unwrap()
+        .scir
+        .convert_schema::<Spice>()
+        .unwrap()
+        .build()
+        .unwrap();
+    Spice
+        .write_scir_netlist_to_file(&scir, netlist_path, NetlistOptions::default())
+        .expect("failed to write netlist");
+    ctx.write_layout(block, gds_path)
+        .expect("failed to write layout");
+}
+
+/// Runs sky130 parasitic extraction (via Magic) on `gds_path`, producing an
+/// extracted SPICE netlist for `cell_name` in `pex_work_dir`.
+fn run_pex(gds_path: &std::path::Path, cell_name: &str, pex_work_dir: &std::path::Path) -> ExtractedDut {
+    std::fs::create_dir_all(pex_work_dir).expect("failed to create PEX work dir");
+    let netlist_path = pex_work_dir.join("extracted.spice");
+    let script_path = pex_work_dir.join("extract.tcl");
+    let pdk_root = std::env::var("SKY130_OPEN_PDK_ROOT")
+        .expect("the SKY130_OPEN_PDK_ROOT environment variable must be set");
+
+    std::fs::write(
+        &script_path,
+        format!(
+            "gds read {gds}\nload {cell}\nselect top cell\nextract do local\nextract all\next2spice lvs\next2spice cthresh 0\next2spice -o {netlist}\nquit -noprompt\n",
+            gds = gds_path.display(),
+            cell = cell_name,
+            netlist = netlist_path.display(),
+        ),
+    )
+    .expect("failed to write PEX extraction script");
+
+    let status = std::process::Command::new("magic")
+        .args(["-dnull", "-noconsole", "-rcfile"])
+        .arg(format!("{pdk_root}/magic/sky130A.magicrc"))
+        .arg(&script_path)
+        .status()
+        .expect("failed to invoke magic for parasitic extraction");
+    assert!(status.success(), "magic parasitic extraction failed");
+
+    ExtractedDut {
+        netlist: netlist_path,
+        cell_name: cell_name.into(),
+    }
+}
+
+/// Compares the pre- and post-layout input-referred offset of a StrongARM
+/// comparator by extracting parasitics from its layout and re-running the
+/// offset bisection against the extracted netlist.
+#[test]
+fn strongarm_pex() {
+    let work_dir = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/build/strongarm_pex"));
+    let gds_path = work_dir.join("layout.gds");
+    let pex_work_dir = work_dir.join("pex");
+    let cell_name = "strong_arm";
+
+    let ctx = sky130_open_ctx_with(SimBackend::Spectre);
+
+    let params = StrongArmParams {
+        nmos_kind: MosKind::Lvt,
+        pmos_kind: MosKind::Lvt,
+        half_tail_w: 2,
+        input_pair_w: 2,
+        inv_input_w: 2,
+        inv_precharge_w: 2,
+        precharge_w: 2,
+        input_kind: InputKind::N,
+        strap_width: 3,
+        strap_on_abutment_edges: true,
+        matching: Matching::None,
+        max_finger_w: 0,
+    };
+    let dut = TileWrapper::new(StrongArm::<Sky130strongarm>::new(params));
+
+    ctx.write_layout(dut.clone(), &gds_path)
+        .expect("failed to write layout");
+
+    let extracted = run_pex(&gds_path, cell_name, &pex_work_dir);
+
+    let pvt = Pvt {
+        corner: Sky130Corner::Tt,
+        voltage: dec!(0.85),
+        temp: dec!(25.0),
+    };
+
+    let schematic_offset = StrongArmTranTb::offset(
+        &ctx,
+        Dut::Schematic(dut),
+        dec!(0.3),
+        true,
+        pvt,
+        SimBackend::Spectre,
+        dec!(0.85),
+        dec!(0.001),
+        20,
+        work_dir.join("offset_schematic"),
+    )
+    .expect("failed to measure schematic offset")
+    .expect("schematic comparator never railed");
+
+    let extracted_offset = StrongArmTranTb::offset(
+        &ctx,
+        Dut::extracted(extracted),
+        dec!(0.3),
+        true,
+        pvt,
+        SimBackend::Spectre,
+        dec!(0.85),
+        dec!(0.001),
+        20,
+        work_dir.join("offset_extracted"),
+    )
+    .expect("failed to measure extracted offset")
+    .expect("extracted comparator never railed");
 
+    println!(
+        "pre-layout offset: {schematic_offset} V, post-layout offset: {extracted_offset} V"
+    );
 }
\ No newline at end of file
@@ -2,7 +2,7 @@
 
 use crate::buffer::{BufferIoSchematic, Inverter, InverterImpl, InverterParams};
 use crate::tiles::{MosKind, MosTileParams, TapIo, TapTileParams, TileKind};
-use atoll::route::{GreedyRouter, ViaMaker};
+use atoll::route::ViaMaker;
 use atoll::{IoBuilder, Orientation, Tile, TileBuilder};
 use serde::{Deserialize, Serialize};
 use std::any::Any;
@@ -18,6 +18,7 @@ use substrate::schematic::schema::Schema;
 use substrate::schematic::ExportsNestedData;
 use sky130pdk::atoll::{MosTileIo, MosTileIoSchematic};
 
+pub mod router;
 pub mod tb;
 
 /// The interface to a clocked differential comparator.
@@ -35,6 +36,21 @@ pub struct ClockedDiffComparatorIo {
     pub vss: InOut<Signal>,
 }
 
+/// The interface to a two-input NAND gate.
+#[derive(Debug, Default, Clone, Io)]
+pub struct Nand2Io {
+    /// The first input.
+    pub a: Input<Signal>,
+    /// The second input.
+    pub b: Input<Signal>,
+    /// The output.
+    pub y: Output<Signal>,
+    /// The VDD rail.
+    pub vdd: InOut<Signal>,
+    /// The VSS rail.
+    pub vss: InOut<Signal>,
+}
+
 /// The input pair device kind of the comparator.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum InputKind {
@@ -56,6 +72,73 @@ impl InputKind {
     }
 }
 
+/// How a differential pair of MOS devices is placed relative to one
+/// another.
+///
+/// Splitting a device into unit fingers and alternating them with its
+/// differential partner trades cell area for reduced sensitivity to
+/// process and thermal gradients across the row.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq, Default)]
+pub enum Matching {
+    /// Each device is a single unit, placed directly beside its partner.
+    #[default]
+    None,
+    /// Each device is split into two unit fingers and alternated `ABAB`.
+    Interdigitated,
+    /// Each device is split into two unit fingers and arranged
+    /// symmetrically as `ABBA`, so the two devices share a common
+    /// centroid.
+    CommonCentroid,
+}
+
+/// Returns, in layout order, which logical device (`false` for the first,
+/// `true` for the second) each unit finger of a [`Matching`]-ed pair
+/// belongs to.
+fn matching_pattern(matching: Matching) -> &'static [bool] {
+    match matching {
+        Matching::None => &[false, true],
+        Matching::Interdigitated => &[false, true, false, true],
+        Matching::CommonCentroid => &[false, true, true, false],
+    }
+}
+
+/// Computes the per-unit-finger placement pattern and width for a
+/// differential pair of devices, combining [`Matching`] with folding: a
+/// device wider than `max_finger_w` is itself split into equal-width
+/// parallel fingers so that no single row element exceeds the per-finger
+/// width limit. `max_finger_w <= 0` disables folding.
+///
+/// Returns `(pattern, unit_w)`, where `pattern[i]` is `false`/`true`
+/// according to which logical device (first/second) unit finger `i`
+/// belongs to, and `unit_w` is the width each unit finger should be drawn
+/// at.
+fn fingers(total_w: i64, matching: Matching, max_finger_w: i64) -> (Vec<bool>, i64) {
+    let device_w = match matching {
+        Matching::None => total_w,
+        Matching::Interdigitated | Matching::CommonCentroid => (total_w + 1) / 2,
+    };
+    let fold = if max_finger_w <= 0 {
+        1
+    } else {
+        (device_w + max_finger_w - 1) / max_finger_w
+    };
+    let unit_w = (device_w + fold - 1) / fold;
+    let pattern = matching_pattern(matching)
+        .iter()
+        .flat_map(|&device| std::iter::repeat(device).take(fold as usize))
+        .collect();
+    (pattern, unit_w)
+}
+
+/// Returns the index of the first unit finger in `pattern` belonging to
+/// the first (`false`) or second (`true`) logical device.
+fn first_finger(pattern: &[bool], device: bool) -> usize {
+    pattern
+        .iter()
+        .position(|&b| b == device)
+        .expect("matching pattern must contain both devices")
+}
+
 /// The parameters of the [`StrongArm`] layout generator.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct StrongArmParams {
@@ -75,6 +158,32 @@ pub struct StrongArmParams {
     pub precharge_w: i64,
     /// The kind of the input pair MOS devices.
     pub input_kind: InputKind,
+    /// The width, in tap-tile columns, of the `vdd`/`vss` power straps run
+    /// on [`TileBuilder::set_top_layer`]'s top layer.
+    ///
+    /// Wider straps lower the resistance of the supply connection to the
+    /// comparator's precharge and tail devices at the cost of cell area.
+    pub strap_width: i64,
+    /// Whether the `vdd`/`vss` straps are pushed out to the left/right
+    /// abutment edges of the half-cell.
+    ///
+    /// [`StrongArm::tile`] mirrors two [`StrongArmHalf`]s about their
+    /// shared abutment edge; setting this means the straps from each half
+    /// land flush against one another, forming one continuous power grid
+    /// across the full comparator instead of two independently-routed
+    /// half-cell grids.
+    pub strap_on_abutment_edges: bool,
+    /// How the input pair, and the precharge/inverter pairs, are placed
+    /// relative to their differential partner.
+    pub matching: Matching,
+    /// The maximum width, in the same units as the `*_w` fields above, of
+    /// a single MOS tile finger.
+    ///
+    /// Devices wider than this are automatically folded into the fewest
+    /// number of equal-width parallel fingers that each stay within the
+    /// limit, keeping every row at a consistent height. A value `<= 0`
+    /// disables folding.
+    pub max_finger_w: i64,
 }
 
 /// A StrongARM latch implementation.
@@ -85,6 +194,8 @@ pub trait StrongArmImpl<PDK: Pdk + Schema> {
     type TapTile: Tile<PDK> + Block<Io = TapIo> + Clone;
     /// A PDK-specific via maker.
     type ViaMaker: ViaMaker<PDK>;
+    /// The routing engine used to complete the layout.
+    type Router: atoll::route::Router;
 
     /// Creates an instance of the MOS tile.
     fn mos(params: MosTileParams) -> Self::MosTile;
@@ -92,6 +203,13 @@ pub trait StrongArmImpl<PDK: Pdk + Schema> {
     fn tap(params: TapTileParams) -> Self::TapTile;
     /// Creates a PDK-specific via maker.
     fn via_maker() -> Self::ViaMaker;
+    /// Creates the router used to route this implementation's layout.
+    ///
+    /// Most implementations should return [`GreedyRouter`](atoll::route::GreedyRouter).
+    /// Implementations whose precharge/inverter rows leave the greedy router
+    /// with opens can return [`PathFinderRouter`](router::PathFinderRouter)
+    /// instead to trade routing runtime for routability.
+    fn router() -> Self::Router;
     /// Additional layout hooks to run after the strongARM layout is complete.
     fn post_layout_hooks(_cell: &mut TileBuilder<'_, PDK>) -> Result<()> {
         Ok(())
@@ -208,10 +326,13 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         
         let input_rail_tail1 = &cell.signal("input_rail_tail", Array::new(2, Signal));
         let io_stic =&cell.signal("io.schematic.top_io.clock", Array::new(1, Signal));
-        let mut tail_pair = (0..2)
+        let (tail_pattern, tail_unit_w) =
+            fingers(self.0.half_tail_w, Matching::None, self.0.max_finger_w);
+        let half_tail_finger_params = MosTileParams::new(input_flavor, input_kind, tail_unit_w);
+        let mut tail_pair = (0..tail_pattern.len())
             .map(|_| {
                 cell.generate_connected(
-                    T::mos(half_tail_params),
+                    T::mos(half_tail_finger_params),
                     MosTileIoSchematic {
                         sd: input_rail_tail1.clone(),
                         g: io_stic.clone(),
@@ -220,24 +341,26 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
                 )
             })
             .collect::<Vec<_>>();
-        cell.connect(tail_pair[0].io().sd[0], input_rail);
-        cell.connect(tail_pair[0].io().sd[1], tail);
-        cell.connect(tail_pair[0].io().g[0], io.schematic.top_io.clock);
-        cell.connect(tail_pair[1].io().sd[0], input_rail);
-        cell.connect(tail_pair[1].io().sd[1], tail);
-        cell.connect(tail_pair[1].io().g[0], io.schematic.top_io.clock);
-
-        let mut ptap = cell.generate(T::tap(TapTileParams::new(TileKind::P, 3)));
-        let ntap = cell.generate(T::tap(TapTileParams::new(TileKind::N, 3)));
+        for unit in tail_pair.iter_mut() {
+            cell.connect(unit.io().sd[0], input_rail);
+            cell.connect(unit.io().sd[1], tail);
+            cell.connect(unit.io().g[0], io.schematic.top_io.clock);
+        }
+
+        let mut ptap = cell.generate(T::tap(TapTileParams::new(TileKind::P, self.0.strap_width)));
+        let mut ntap = cell.generate(T::tap(TapTileParams::new(TileKind::N, self.0.strap_width)));
         cell.connect(ptap.io().x, io.schematic.top_io.vss);
         cell.connect(ntap.io().x, io.schematic.top_io.vdd);
 
         let tail_int = cell.signal("tail_int", Array::new(2, Signal));
         let iosti = cell.signal("io.schematic.top_io.input", Array::new(1, Signal));
-        let mut input_pair = (0..2)
-            .map(|i| {
+        let (input_pattern, input_unit_w) =
+            fingers(self.0.input_pair_w, self.0.matching, self.0.max_finger_w);
+        let input_pair_finger_params = MosTileParams::new(input_flavor, input_kind, input_unit_w);
+        let mut input_pair = (0..input_pattern.len())
+            .map(|_| {
                 cell.generate_connected(
-                    T::mos(input_pair_params),
+                    T::mos(input_pair_finger_params),
                     MosTileIoSchematic {
                         sd: tail_int.clone(),
                         g: iosti.clone(),
@@ -246,12 +369,16 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
                 )
             })
             .collect::<Vec<_>>();
-        cell.connect(input_pair[0].io().sd[0], tail);
-        cell.connect(input_pair[0].io().sd[1], intn);
-        cell.connect(tail_pair[0].io().g[0], io.schematic.top_io.input.p);
-        cell.connect(input_pair[1].io().sd[0], tail);
-        cell.connect(input_pair[1].io().sd[1], intp);
-        cell.connect(tail_pair[1].io().g[0], io.schematic.top_io.input.n);
+        for (i, unit) in input_pair.iter_mut().enumerate() {
+            cell.connect(unit.io().sd[0], tail);
+            if input_pattern[i] {
+                cell.connect(unit.io().sd[1], intp);
+                cell.connect(unit.io().g[0], io.schematic.top_io.input.n);
+            } else {
+                cell.connect(unit.io().sd[1], intn);
+                cell.connect(unit.io().g[0], io.schematic.top_io.input.p);
+            }
+        }
 
         let input_rail3 = cell.signal("input_rail", Array::new(2, Signal)); 
         let input_rail4 = cell.signal("input_rail", Array::new(1, Signal));
@@ -272,11 +399,15 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         let iostiop = cell.signal("io.schematic.top_io.output.p", Array::new(1, Signal));
         let intpiostio = cell.signal("intp_io.schematic.top_io.output", Array::new(2, Signal));
         let iostion = cell.signal("io.schematic.top_io.output.n", Array::new(1, Signal));
-        let mut inv_input_pair = (0..2)
+        let (inv_input_pattern, inv_input_unit_w) =
+            fingers(self.0.inv_input_w, self.0.matching, self.0.max_finger_w);
+        let inv_input_finger_params =
+            MosTileParams::new(input_flavor, input_kind, inv_input_unit_w);
+        let mut inv_input_pair = (0..inv_input_pattern.len())
             .map(|i| {
                 cell.generate_connected(
-                    T::mos(inv_input_params),
-                    if i == 0 {
+                    T::mos(inv_input_finger_params),
+                    if !inv_input_pattern[i] {
                         MosTileIoSchematic {
                             sd: intniostio.clone(),
                             g: iostiop.clone(),
@@ -292,12 +423,17 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
                 )
             })
             .collect::<Vec<_>>();
-        cell.connect(inv_input_pair[0].io().sd[0], intn);
-        cell.connect(inv_input_pair[0].io().sd[1], io.schematic.top_io.output.n);
-        cell.connect(inv_input_pair[0].io().g[0], io.schematic.top_io.output.p);
-        cell.connect(inv_input_pair[1].io().sd[0], intp);
-        cell.connect(inv_input_pair[1].io().sd[1], io.schematic.top_io.output.p);
-        cell.connect(inv_input_pair[1].io().g[0], io.schematic.top_io.output.n);
+        for (i, unit) in inv_input_pair.iter_mut().enumerate() {
+            if inv_input_pattern[i] {
+                cell.connect(unit.io().sd[0], intp);
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.p);
+                cell.connect(unit.io().g[0], io.schematic.top_io.output.n);
+            } else {
+                cell.connect(unit.io().sd[0], intn);
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.n);
+                cell.connect(unit.io().g[0], io.schematic.top_io.output.p);
+            }
+        }
 
         
         let input_rail5 = cell.signal("input_rail", Array::new(2, Signal));
@@ -317,10 +453,14 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
 
         let precharge_rail_ios = cell.signal("precharge_rail_io.schematic", Array::new(2, Signal));
         let iostio1 = cell.signal("io.schematic.top_io.output", Array::new(1, Signal));
-        let mut inv_precharge_pair = (0..2)
-            .map(|i| {
+        let (inv_precharge_pattern, inv_precharge_unit_w) =
+            fingers(self.0.inv_precharge_w, self.0.matching, self.0.max_finger_w);
+        let inv_precharge_finger_params =
+            MosTileParams::new(precharge_flavor, precharge_kind, inv_precharge_unit_w);
+        let mut inv_precharge_pair = (0..inv_precharge_pattern.len())
+            .map(|_| {
                 cell.generate_connected(
-                    T::mos(inv_precharge_params),
+                    T::mos(inv_precharge_finger_params),
                     MosTileIoSchematic {
                         sd: precharge_rail_ios.clone(),
                         g: iostio1.clone(),
@@ -330,13 +470,17 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
             })
             .collect::<Vec<_>>();
 
-        cell.connect(inv_precharge_pair[0].io().sd[0], precharge_rail);
-        cell.connect(inv_precharge_pair[0].io().sd[1], io.schematic.top_io.output.n);
-        cell.connect(inv_precharge_pair[0].io().g[0], io.schematic.top_io.output.p);
-        cell.connect(inv_precharge_pair[1].io().sd[0], precharge_rail);
-        cell.connect(inv_precharge_pair[1].io().sd[1], io.schematic.top_io.output.p);
-        cell.connect(inv_precharge_pair[1].io().g[0], io.schematic.top_io.output.n);
-        
+        for (i, unit) in inv_precharge_pair.iter_mut().enumerate() {
+            cell.connect(unit.io().sd[0], precharge_rail);
+            if inv_precharge_pattern[i] {
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.p);
+                cell.connect(unit.io().g[0], io.schematic.top_io.output.n);
+            } else {
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.n);
+                cell.connect(unit.io().g[0], io.schematic.top_io.output.p);
+            }
+        }
+
 
         let precharge_rail1 = cell.signal("precharge_rail", Array::new(2, Signal));
         let precharge_rail2 = cell.signal("precharge_rail", Array::new(1, Signal));
@@ -355,10 +499,14 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         let precharge_rail_ios = cell.signal("precharge_rail_io.schematic", Array::new(1, Signal));
         let iost_ioc = cell.signal("io.schematic.top_io.clock", Array::new(1, Signal));
 
-        let mut precharge_pair_a = (0..2)
-            .map(|i| {
+        let (precharge_pair_a_pattern, precharge_pair_a_unit_w) =
+            fingers(self.0.precharge_w, self.0.matching, self.0.max_finger_w);
+        let precharge_pair_a_finger_params =
+            MosTileParams::new(precharge_flavor, precharge_kind, precharge_pair_a_unit_w);
+        let mut precharge_pair_a = (0..precharge_pair_a_pattern.len())
+            .map(|_| {
                 cell.generate_connected(
-                    T::mos(precharge_params),
+                    T::mos(precharge_pair_a_finger_params),
                     MosTileIoSchematic {
                         sd: precharge_rail_ios.clone(),
                         g: iost_ioc.clone(),
@@ -367,14 +515,17 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
                 )
             })
             .collect::<Vec<_>>();
-        
-            cell.connect(inv_precharge_pair[0].io().sd[0], precharge_rail);
-            cell.connect(inv_precharge_pair[0].io().sd[1], io.schematic.top_io.output.n);
-            cell.connect(inv_precharge_pair[0].io().g[0], io.schematic.top_io.clock);
-            cell.connect(inv_precharge_pair[1].io().sd[0], precharge_rail);
-            cell.connect(inv_precharge_pair[1].io().sd[1],  io.schematic.top_io.output.p);
-            cell.connect(inv_precharge_pair[1].io().g[0], io.schematic.top_io.clock);
-        
+
+        for (i, unit) in precharge_pair_a.iter_mut().enumerate() {
+            cell.connect(unit.io().sd[0], precharge_rail);
+            if precharge_pair_a_pattern[i] {
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.p);
+            } else {
+                cell.connect(unit.io().sd[1], io.schematic.top_io.output.n);
+            }
+            cell.connect(unit.io().g[0], io.schematic.top_io.clock);
+        }
+
         let precharge_rail3 = cell.signal("precharge_rail", Array::new(2, Signal)); 
         let precharge_rail4 = cell.signal("precharge_rail", Array::new(1, Signal));
 
@@ -392,10 +543,14 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
 
         let precharge_rail_int = cell.signal("precharge_rail_int", Array::new(2, Signal));
         let iostic1 = cell.signal("io.schematic.top_io.clock", Array::new(1, Signal));
-        let mut precharge_pair_b = (0..2)
-            .map(|i| {
+        let (precharge_pair_b_pattern, precharge_pair_b_unit_w) =
+            fingers(self.0.precharge_w, self.0.matching, self.0.max_finger_w);
+        let precharge_pair_b_finger_params =
+            MosTileParams::new(precharge_flavor, precharge_kind, precharge_pair_b_unit_w);
+        let mut precharge_pair_b = (0..precharge_pair_b_pattern.len())
+            .map(|_| {
                 cell.generate_connected(
-                    T::mos(precharge_params),
+                    T::mos(precharge_pair_b_finger_params),
                     MosTileIoSchematic {
                         sd: precharge_rail_int.clone(),
                         g: iostic1.clone(),
@@ -404,12 +559,15 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
                 )
             })
             .collect::<Vec<_>>();
-        cell.connect(precharge_pair_b[0].io().sd[0], precharge_rail);
-        cell.connect(precharge_pair_b[0].io().sd[1], intn);
-        cell.connect(precharge_pair_b[0].io().g[0], io.schematic.top_io.clock);
-        cell.connect(precharge_pair_b[1].io().sd[0], precharge_rail);
-        cell.connect(precharge_pair_b[1].io().sd[1],  intp);
-        cell.connect(precharge_pair_b[1].io().g[0], io.schematic.top_io.clock);
+        for (i, unit) in precharge_pair_b.iter_mut().enumerate() {
+            cell.connect(unit.io().sd[0], precharge_rail);
+            if precharge_pair_b_pattern[i] {
+                cell.connect(unit.io().sd[1], intp);
+            } else {
+                cell.connect(unit.io().sd[1], intn);
+            }
+            cell.connect(unit.io().g[0], io.schematic.top_io.clock);
+        }
 
         let precharge_rail5 = cell.signal("precharge_rail", Array::new(2, Signal));
         let precharge_rail6 = cell.signal("precharge_rail", Array::new(1, Signal));
@@ -427,6 +585,11 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         cell.connect(precharge_pair_b_dummy.io().g[0], precharge_rail);
 
         let mut prev = ntap.lcm_bounds();
+        // The rightmost column actually reached by any row, i.e. the last
+        // unit finger of the widest row below rather than just a row's
+        // dummy column, so the rail straps below sit at the row stack's
+        // real width instead of undershooting it.
+        let mut row_width = prev;
 
         let mut rows = [
             (&mut precharge_pair_a_dummy, &mut precharge_pair_a),
@@ -445,16 +608,38 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
             dummy.align_rect_mut(prev, AlignMode::Left, 0);
             dummy.align_rect_mut(prev, AlignMode::Beneath, 0);
             prev = dummy.lcm_bounds();
-            mos_pair[0].align_rect_mut(prev, AlignMode::Bottom, 0);
-            mos_pair[0].align_rect_mut(prev, AlignMode::ToTheRight, 0);
-            let left_rect = mos_pair[0].lcm_bounds();
-            mos_pair[1].align_rect_mut(left_rect, AlignMode::Bottom, 0);
-            mos_pair[1].align_rect_mut(left_rect, AlignMode::ToTheRight, 0);
+            // Each unit finger (2 for an unmatched pair, more once
+            // `matching` splits a device) is placed to the right of the
+            // previous one, keeping `dummy` as the row's outermost column.
+            let mut col = prev;
+            for unit in mos_pair.iter_mut() {
+                unit.align_rect_mut(col, AlignMode::Bottom, 0);
+                unit.align_rect_mut(col, AlignMode::ToTheRight, 0);
+                col = unit.lcm_bounds();
+            }
+            // Different rows can legitimately end up with different finger
+            // counts (see `Matching`/folding above), so `row_width` has to
+            // be the widest row seen so far, not just the last one placed.
+            if col.right() > row_width.right() {
+                row_width = col;
+            }
         }
 
         ptap.align_rect_mut(prev, AlignMode::Left, 0);
         ptap.align_rect_mut(prev, AlignMode::Beneath, 0);
 
+        if self.0.strap_on_abutment_edges {
+            // Shift both rail straps out past the right edge of the row
+            // stack, to the half-cell's abutment edge, so they land flush against the mirrored
+            // half's straps in `StrongArm::tile`, forming one continuous
+            // `vdd`/`vss` grid instead of two independently-routed half
+            // grids. `ntap` needs the same treatment as `ptap` here: it
+            // backs the same abutting mirror seam, just on the opposite
+            // rail.
+            ptap.align_rect_mut(row_width, AlignMode::ToTheRight, 0);
+            ntap.align_rect_mut(row_width, AlignMode::ToTheRight, 0);
+        }
+
         let ptap = cell.draw(ptap)?;
         let ntap = cell.draw(ntap)?;
         let tail_pair = tail_pair
@@ -489,27 +674,32 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         let _precharge_pair_b_dummy = cell.draw(precharge_pair_b_dummy)?;
 
         cell.set_top_layer(2);
-        cell.set_router(GreedyRouter::new());
+        cell.set_router(T::router());
         cell.set_via_maker(T::via_maker());
 
+        let input_a = first_finger(&input_pattern, false);
+        let input_b = first_finger(&input_pattern, true);
+        let inv_input_a = first_finger(&inv_input_pattern, false);
+        let inv_input_b = first_finger(&inv_input_pattern, true);
+
         io.layout.top_io.vdd.set_primary(ntap.layout.io().x.primary);
         io.layout.top_io.vss.set_primary(ptap.layout.io().x.primary);
-        io.layout.input_d.n.merge(input_pair[0].layout.io().sd[0].clone());
-        io.layout.input_d.p.merge(input_pair[1].layout.io().sd[1].clone());
+        io.layout.input_d.n.merge(input_pair[input_a].layout.io().sd[0].clone());
+        io.layout.input_d.p.merge(input_pair[input_b].layout.io().sd[1].clone());
         io.layout.tail_d.merge(tail_pair[0].layout.io().sd[1].clone());
         io.layout.top_io.clock.merge(tail_pair[0].layout.io().g[0].clone());
-        io.layout.top_io.input.p.merge(input_pair[0].layout.io().g[0].clone());
-        io.layout.top_io.input.n.merge(input_pair[1].layout.io().g[0].clone());
+        io.layout.top_io.input.p.merge(input_pair[input_a].layout.io().g[0].clone());
+        io.layout.top_io.input.n.merge(input_pair[input_b].layout.io().g[0].clone());
         io.layout
             .top_io
             .output
             .p
-            .merge(inv_nmos_pair[1].layout.io().sd[1].clone());
+            .merge(inv_nmos_pair[inv_input_b].layout.io().sd[1].clone());
         io.layout
             .top_io
             .output
             .n
-            .merge(inv_nmos_pair[0].layout.io().sd[1].clone());
+            .merge(inv_nmos_pair[inv_input_a].layout.io().sd[1].clone());
 
         Ok(((), ()))
     }
@@ -584,7 +774,7 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
         let right_half = cell.draw(right_half)?;
 
         cell.set_top_layer(2);
-        cell.set_router(GreedyRouter::new());
+        cell.set_router(T::router());
         cell.set_via_maker(T::via_maker());
 
         io.layout.vdd.merge(left_half.layout.io().top_io.vdd);
@@ -632,9 +822,27 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmImpl<PDK> + Any> Tile<PDK> for Stron
     }
 }
 
+/// The top layer and routing engine used by a top-level StrongARM tile
+/// (one that owns its own [`TileBuilder`], unlike a sub-block such as
+/// [`StrongArmHalf`]).
+///
+/// Shared by [`StrongArmWithOutputBuffersImpl`] and [`SrLatchImpl`] so each
+/// can pick a top layer and router independently of the
+/// [`StrongArmImpl::Router`] used to route the bare [`StrongArm`] tile
+/// nested inside them.
+pub trait TopTileImpl<PDK: Pdk + Schema> {
+    /// The ATOLL top layer this tile's layout is routed up to.
+    const TOP_LAYER: i64;
+    /// The routing engine used to complete this tile's layout.
+    type Router: atoll::route::Router;
+
+    /// Creates the router used to route this tile's layout.
+    fn router() -> Self::Router;
+}
+
 /// A StrongARM latch with output buffers implementation.
 pub trait StrongArmWithOutputBuffersImpl<PDK: Pdk + Schema>:
-    StrongArmImpl<PDK> + InverterImpl<PDK>
+    StrongArmImpl<PDK> + InverterImpl<PDK> + TopTileImpl<PDK>
 {
     /// The spacing between the StrongARM and the buffers in ATOLL grid coordinates.
     const BUFFER_SPACING: i64;
@@ -645,19 +853,53 @@ pub trait StrongArmWithOutputBuffersImpl<PDK: Pdk + Schema>:
     }
 }
 
+/// A tapered chain of inverter buffer stages driving one side of a
+/// [`StrongArmWithOutputBuffers`] output.
+///
+/// Stages are stored first-to-last: stage 0's `din` is driven directly by
+/// the comparator's latch output, and each subsequent stage's `din` is
+/// chained to the previous stage's `dout`. Sizing the first stage light
+/// keeps it from loading the latch's output nodes, while the last stage can
+/// be sized to drive a large off-tile load.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct BufferChainParams(Vec<InverterParams>);
+
+impl BufferChainParams {
+    /// Creates a buffer chain from an explicit, stage-ordered list of
+    /// per-stage [`InverterParams`].
+    pub fn new(stages: Vec<InverterParams>) -> Self {
+        Self(stages)
+    }
+
+    /// The number of inverter stages in the chain.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the chain has no stages.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The per-stage [`InverterParams`], first-to-last.
+    pub fn stages(&self) -> &[InverterParams] {
+        &self.0
+    }
+}
+
 /// A StrongARM latch with output buffers.
 // Layout assumes that PDK layer stack has a vertical layer 0.
-#[derive_where::derive_where(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive_where::derive_where(Clone, Debug, Hash, PartialEq, Eq)]
 #[derive(Serialize, Deserialize)]
 pub struct StrongArmWithOutputBuffers<T>(
     StrongArmParams,
-    InverterParams,
+    BufferChainParams,
     #[serde(bound(deserialize = ""))] PhantomData<fn() -> T>,
 );
 
 impl<T> StrongArmWithOutputBuffers<T> {
     /// Creates a new [`StrongArmWithOutputBuffers`].
-    pub const fn new(sa_params: StrongArmParams, buf_params: InverterParams) -> Self {
+    pub const fn new(sa_params: StrongArmParams, buf_params: BufferChainParams) -> Self {
         Self(sa_params, buf_params, PhantomData)
     }
 }
@@ -711,39 +953,267 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmWithOutputBuffersImpl<PDK> + Any> Ti
             },
         );
 
-        let right_buf = cell
+        // An even number of inverter stages preserves polarity and an odd
+        // number flips it, so each chain's starting tap (not its per-stage
+        // wiring) is chosen from `out.p`/`out.n` so that after
+        // `self.1.len()` inversions the final stage lands on the correct
+        // `output.p`/`output.n` rail.
+        let odd_stages = self.1.len() % 2 == 1;
+        let (right_start, left_start) = if odd_stages {
+            (out.p, out.n)
+        } else {
+            (out.n, out.p)
+        };
+
+        let mut right_chain = Vec::with_capacity(self.1.len());
+        let mut din = right_start;
+        for (i, params) in self.1.stages().iter().enumerate() {
+            let last = i + 1 == self.1.len();
+            let dout = if last {
+                io.schematic.output.n
+            } else {
+                cell.signal(format!("right_buf{i}_out"), Signal)
+            };
+            let buf = cell
+                .generate_connected(
+                    Inverter::<T>::new(*params),
+                    BufferIoSchematic {
+                        din,
+                        dout,
+                        vdd: io.schematic.vdd,
+                        vss: io.schematic.vss,
+                    },
+                )
+                .align(&strongarm, AlignMode::CenterVertical, 0);
+            let buf = if i == 0 {
+                buf.align(&strongarm, AlignMode::ToTheRight, T::BUFFER_SPACING)
+            } else {
+                buf.align(&right_chain[i - 1], AlignMode::ToTheRight, T::BUFFER_SPACING)
+            };
+            din = dout;
+            right_chain.push(buf);
+        }
+
+        let mut left_chain = Vec::with_capacity(self.1.len());
+        let mut din = left_start;
+        for (i, params) in self.1.stages().iter().enumerate() {
+            let last = i + 1 == self.1.len();
+            let dout = if last {
+                io.schematic.output.p
+            } else {
+                cell.signal(format!("left_buf{i}_out"), Signal)
+            };
+            let buf = cell
+                .generate_connected(
+                    Inverter::<T>::new(*params),
+                    BufferIoSchematic {
+                        din,
+                        dout,
+                        vdd: io.schematic.vdd,
+                        vss: io.schematic.vss,
+                    },
+                )
+                .orient(Orientation::ReflectHoriz)
+                .align(&strongarm, AlignMode::CenterVertical, 0);
+            let buf = if i == 0 {
+                buf.align(&strongarm, AlignMode::ToTheLeft, -T::BUFFER_SPACING)
+            } else {
+                buf.align(&left_chain[i - 1], AlignMode::ToTheLeft, -T::BUFFER_SPACING)
+            };
+            din = dout;
+            left_chain.push(buf);
+        }
+
+        let strongarm = cell.draw(strongarm)?;
+        let right_chain = right_chain
+            .into_iter()
+            .map(|inst| cell.draw(inst))
+            .collect::<Result<Vec<_>>>()?;
+        let left_chain = left_chain
+            .into_iter()
+            .map(|inst| cell.draw(inst))
+            .collect::<Result<Vec<_>>>()?;
+
+        cell.set_top_layer(<T as TopTileImpl<PDK>>::TOP_LAYER);
+        cell.set_router(<T as TopTileImpl<PDK>>::router());
+        cell.set_via_maker(<T as StrongArmImpl<PDK>>::via_maker());
+
+        io.layout.vdd.merge(strongarm.layout.io().vdd);
+        io.layout.vss.merge(strongarm.layout.io().vss);
+        io.layout.clock.merge(strongarm.layout.io().clock);
+        io.layout.input.p.merge(strongarm.layout.io().input.p);
+        io.layout.input.n.merge(strongarm.layout.io().input.n);
+        io.layout.output.p.merge(
+            left_chain
+                .last()
+                .expect("buffer chain must have at least one stage")
+                .layout
+                .io()
+                .dout,
+        );
+        io.layout.output.n.merge(
+            right_chain
+                .last()
+                .expect("buffer chain must have at least one stage")
+                .layout
+                .io()
+                .dout,
+        );
+
+        <T as StrongArmWithOutputBuffersImpl<PDK>>::post_layout_hooks(cell)?;
+
+        Ok(((), ()))
+    }
+}
+
+/// A two-input NAND gate implementation, used by [`StrongArmWithSrLatch`] to
+/// build its holding SR latch.
+pub trait NandImpl<PDK: Pdk + Schema> {
+    /// The NAND2 tile.
+    type Nand2Tile: Tile<PDK> + Block<Io = Nand2Io> + Clone;
+    /// A PDK-specific via maker for the NAND2 tile's layout.
+    type Nand2ViaMaker: ViaMaker<PDK>;
+
+    /// Creates an instance of the NAND2 tile.
+    fn nand2() -> Self::Nand2Tile;
+    /// Creates a PDK-specific via maker for the NAND2 tile's layout.
+    fn nand2_via_maker() -> Self::Nand2ViaMaker;
+}
+
+/// A StrongARM latch with an SR-latch holding stage implementation.
+pub trait SrLatchImpl<PDK: Pdk + Schema>:
+    StrongArmImpl<PDK> + NandImpl<PDK> + TopTileImpl<PDK>
+{
+    /// The spacing between the StrongARM and the SR latch in ATOLL grid coordinates.
+    const LATCH_SPACING: i64;
+
+    /// Additional layout hooks to run after the layout is complete.
+    fn post_layout_hooks(_cell: &mut TileBuilder<'_, PDK>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// A StrongARM latch with a cross-coupled NAND SR latch holding its decision
+/// across the reset phase.
+///
+/// A raw [`StrongArm`] drives both `output.p`/`output.n` high during the
+/// reset/precharge phase, so its decision is only valid during the evaluate
+/// phase. This wraps a [`StrongArm`] with two cross-coupled NAND2 gates:
+/// each gate's output feeds the other's second input, with the StrongARM's
+/// raw `out.p`/`out.n` (active-low while the latch is resolving, high
+/// otherwise) on the remaining inputs. This holds the resolved decision as a
+/// stable, full-swing digital value on [`ClockedDiffComparatorIo::output`]
+/// even once the StrongARM returns to reset.
+// Layout assumes that PDK layer stack has a vertical layer 0.
+#[derive_where::derive_where(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct StrongArmWithSrLatch<T>(
+    StrongArmParams,
+    #[serde(bound(deserialize = ""))] PhantomData<fn() -> T>,
+);
+
+impl<T> StrongArmWithSrLatch<T> {
+    /// Creates a new [`StrongArmWithSrLatch`].
+    pub const fn new(sa_params: StrongArmParams) -> Self {
+        Self(sa_params, PhantomData)
+    }
+}
+
+impl<T: Any> Block for StrongArmWithSrLatch<T> {
+    type Io = ClockedDiffComparatorIo;
+
+    fn id() -> ArcStr {
+        substrate::arcstr::literal!("strong_arm_with_sr_latch")
+    }
+
+    // todo: include parameters in name
+    fn name(&self) -> ArcStr {
+        substrate::arcstr::literal!("strong_arm_with_sr_latch")
+    }
+
+    fn io(&self) -> Self::Io {
+        Default::default()
+    }
+}
+
+impl<T: Any> ExportsNestedData for StrongArmWithSrLatch<T> {
+    type NestedData = ();
+}
+
+impl<T: Any> ExportsLayoutData for StrongArmWithSrLatch<T> {
+    type LayoutData = ();
+}
+
+impl<PDK: Pdk + Schema + Sized, T: SrLatchImpl<PDK> + Any> Tile<PDK> for StrongArmWithSrLatch<T> {
+    fn tile<'a>(
+        &self,
+        io: IoBuilder<'a, Self>,
+        cell: &mut TileBuilder<'a, PDK>,
+    ) -> substrate::error::Result<(
+        <Self as ExportsNestedData>::NestedData,
+        <Self as ExportsLayoutData>::LayoutData,
+    )> {
+        let out = cell.signal("out", DiffPair::default());
+
+        let strongarm = cell.generate_connected(
+            StrongArm::<T>::new(self.0),
+            ClockedDiffComparatorIoSchematic {
+                input: io.schematic.input.clone(),
+                output: out.clone(),
+                clock: io.schematic.clock,
+                vdd: io.schematic.vdd,
+                vss: io.schematic.vss,
+            },
+        );
+
+        // Cross-coupled NAND SR latch: `q` is the NAND of `out.p` and the
+        // other gate's output `qn`, and `qn` is the NAND of `out.n` and `q`.
+        // While the StrongARM is resolving, the winning side's raw output
+        // stays high from precharge and the losing side's discharges low,
+        // setting `q`/`qn` accordingly; once both raw outputs return high
+        // on reset, each NAND simply re-feeds the other's held value, so
+        // the decision persists.
+        let q = cell.signal("q", Signal);
+        let qn = cell.signal("qn", Signal);
+        // Same polarity as the layout pins below: `qn` drives `output.p`.
+        cell.connect(qn, io.schematic.output.p);
+        cell.connect(q, io.schematic.output.n);
+
+        let nand_p = cell
             .generate_connected(
-                Inverter::<T>::new(self.1),
-                BufferIoSchematic {
-                    din: out.p,
-                    dout: io.schematic.output.n,
+                T::nand2(),
+                Nand2IoSchematic {
+                    a: out.p,
+                    b: qn,
+                    y: q,
                     vdd: io.schematic.vdd,
                     vss: io.schematic.vss,
                 },
             )
             .align(&strongarm, AlignMode::CenterVertical, 0)
-            .align(&strongarm, AlignMode::ToTheRight, T::BUFFER_SPACING);
+            .align(&strongarm, AlignMode::ToTheRight, T::LATCH_SPACING);
 
-        let left_buf = cell
+        let nand_n = cell
             .generate_connected(
-                Inverter::<T>::new(self.1),
-                BufferIoSchematic {
-                    din: out.n,
-                    dout: io.schematic.output.p,
+                T::nand2(),
+                Nand2IoSchematic {
+                    a: out.n,
+                    b: q,
+                    y: qn,
                     vdd: io.schematic.vdd,
                     vss: io.schematic.vss,
                 },
             )
             .orient(Orientation::ReflectHoriz)
             .align(&strongarm, AlignMode::CenterVertical, 0)
-            .align(&strongarm, AlignMode::ToTheLeft, -T::BUFFER_SPACING);
+            .align(&strongarm, AlignMode::ToTheLeft, -T::LATCH_SPACING);
 
         let strongarm = cell.draw(strongarm)?;
-        let right_buf = cell.draw(right_buf)?;
-        let left_buf = cell.draw(left_buf)?;
+        let nand_p = cell.draw(nand_p)?;
+        let nand_n = cell.draw(nand_n)?;
 
-        cell.set_top_layer(2);
-        cell.set_router(GreedyRouter::new());
+        cell.set_top_layer(<T as TopTileImpl<PDK>>::TOP_LAYER);
+        cell.set_router(<T as TopTileImpl<PDK>>::router());
         cell.set_via_maker(<T as StrongArmImpl<PDK>>::via_maker());
 
         io.layout.vdd.merge(strongarm.layout.io().vdd);
@@ -751,11 +1221,83 @@ impl<PDK: Pdk + Schema + Sized, T: StrongArmWithOutputBuffersImpl<PDK> + Any> Ti
         io.layout.clock.merge(strongarm.layout.io().clock);
         io.layout.input.p.merge(strongarm.layout.io().input.p);
         io.layout.input.n.merge(strongarm.layout.io().input.n);
-        io.layout.output.p.merge(left_buf.layout.io().dout);
-        io.layout.output.n.merge(right_buf.layout.io().dout);
+        // `nand_n`'s output (`qn`) tracks `out.p`'s polarity -- it goes high
+        // exactly when `out.n` is driven low by a `Pos` decision -- so it,
+        // not `nand_p`'s `q`, is what belongs on `output.p` (and vice
+        // versa); feeding `q`/`qn` straight across would present the
+        // logical complement of the comparator's decision.
+        io.layout.output.p.merge(nand_n.layout.io().y);
+        io.layout.output.n.merge(nand_p.layout.io().y);
 
-        <T as StrongArmWithOutputBuffersImpl<PDK>>::post_layout_hooks(cell)?;
+        <T as SrLatchImpl<PDK>>::post_layout_hooks(cell)?;
 
         Ok(((), ()))
     }
+}
+
+#[test]
+fn fingers_with_no_matching_and_no_folding_is_one_unit_per_device() {
+    assert_eq!(fingers(6, Matching::None, 0), (vec![false, true], 6));
+}
+
+#[test]
+fn fingers_interdigitated_splits_each_device_into_two_units() {
+    // device_w = ceil(6 / 2) = 3, no folding.
+    assert_eq!(
+        fingers(6, Matching::Interdigitated, 0),
+        (vec![false, true, false, true], 3)
+    );
+}
+
+#[test]
+fn fingers_common_centroid_splits_each_device_into_two_units() {
+    // device_w = ceil(6 / 2) = 3, no folding.
+    assert_eq!(
+        fingers(6, Matching::CommonCentroid, 0),
+        (vec![false, true, true, false], 3)
+    );
+}
+
+#[test]
+fn fingers_folds_a_device_not_evenly_divisible_by_max_finger_w() {
+    // device_w = 5, fold = ceil(5 / 2) = 3, unit_w = ceil(5 / 3) = 2.
+    assert_eq!(
+        fingers(5, Matching::None, 2),
+        (
+            vec![false, false, false, true, true, true],
+            2
+        )
+    );
+}
+
+#[test]
+fn fingers_combines_common_centroid_matching_with_folding() {
+    // device_w = ceil(5 / 2) = 3, fold = ceil(3 / 2) = 2, unit_w = ceil(3 / 2) = 2.
+    assert_eq!(
+        fingers(5, Matching::CommonCentroid, 2),
+        (
+            vec![false, false, true, true, true, true, false, false],
+            2
+        )
+    );
+}
+
+#[test]
+fn matching_pattern_returns_the_expected_shape_per_variant() {
+    assert_eq!(matching_pattern(Matching::None), &[false, true]);
+    assert_eq!(
+        matching_pattern(Matching::Interdigitated),
+        &[false, true, false, true]
+    );
+    assert_eq!(
+        matching_pattern(Matching::CommonCentroid),
+        &[false, true, true, false]
+    );
+}
+
+#[test]
+fn first_finger_finds_each_device_in_a_common_centroid_pattern() {
+    let pattern = matching_pattern(Matching::CommonCentroid);
+    assert_eq!(first_finger(pattern, false), 0);
+    assert_eq!(first_finger(pattern, true), 1);
 }
\ No newline at end of file
@@ -0,0 +1,737 @@
+//! Routing engines for StrongARM layout generators.
+//!
+//! [`StrongArmImpl`](crate::strongarm::StrongArmImpl) implementations pick a
+//! routing engine via the associated `Router` type. [`GreedyRouter`] (from
+//! `atoll`) routes nets independently in a single pass and is fast, but can
+//! leave opens when the precharge/inverter rows are congested. This module
+//! adds [`PathFinderRouter`], a negotiated-congestion router in the style of
+//! the PathFinder FPGA place-and-route algorithm, and [`LeeMazeRouter`], a
+//! maze router based on Lee's algorithm, as alternatives for designs where
+//! greedy routing fails.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use atoll::route::{PointState, Router, RoutingState};
+
+/// The per-iteration growth factor applied to a node's `present_cost` once
+/// it becomes overused.
+const DEFAULT_PRESENT_COST_GROWTH: f64 = 2.0;
+/// The cost added to a node's `history_cost` for each unit of overuse
+/// observed in an iteration.
+const DEFAULT_HISTORY_COST_STEP: f64 = 1.0;
+/// The maximum number of rip-up-and-reroute iterations to attempt before
+/// returning the routing from the last iteration, overused or not.
+const DEFAULT_MAX_ITERATIONS: usize = 50;
+/// Nets with more terminals than this are treated as high-fanout shared
+/// rails (`vdd`/`vss`/`clock`, which fan out to every tap/gate in the
+/// tile) rather than point-to-point signal nets, since `to_connect` carries
+/// no net names for the router to key off of.
+const SHARED_NET_TERMINAL_THRESHOLD: usize = 2;
+
+/// A grid node's identity: `(layer, x, y)` in ATOLL LCM-grid coordinates.
+type NodeId = (i64, i64, i64);
+
+/// Enumerates every node in `state`'s grid in the same `(layer, x, y)`
+/// nesting order `atoll` uses to build each `to_connect` entry, so a
+/// `to_connect[net]` point-state vector can be zipped against this list to
+/// recover the coordinates it refers to.
+fn grid_nodes(shape: (i64, i64, i64)) -> Vec<NodeId> {
+    let (layers, nx, ny) = shape;
+    let mut nodes = Vec::with_capacity((layers.max(0) * nx.max(0) * ny.max(0)) as usize);
+    for layer in 0..layers {
+        for x in 0..nx {
+            for y in 0..ny {
+                nodes.push((layer, x, y));
+            }
+        }
+    }
+    nodes
+}
+
+/// The terminal nodes `net` must be connected to, recovered by zipping
+/// `points` (one [`PointState`] per grid node, in [`grid_nodes`] order)
+/// against the grid and keeping the nodes `points` marks as already
+/// belonging to `net`.
+fn terminals_for_net(points: &[PointState], nodes: &[NodeId], net: usize) -> Vec<NodeId> {
+    nodes
+        .iter()
+        .zip(points.iter())
+        .filter_map(|(&node, point)| match point {
+            PointState::Routed { net: n } if *n == net => Some(node),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The nodes reachable from `node` in one step: the four same-layer
+/// grid-adjacent neighbors, plus a via step to the layer directly above or
+/// below at the same `(x, y)`. Out-of-bounds steps are dropped.
+fn grid_adjacent(node: NodeId, shape: (i64, i64, i64)) -> Vec<NodeId> {
+    let (layers, nx, ny) = shape;
+    let (layer, x, y) = node;
+    let mut adjacent = Vec::with_capacity(6);
+    for (dx, dy) in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
+        let (nx2, ny2) = (x + dx, y + dy);
+        if nx2 >= 0 && nx2 < nx && ny2 >= 0 && ny2 < ny {
+            adjacent.push((layer, nx2, ny2));
+        }
+    }
+    for dl in [-1, 1] {
+        let next_layer = layer + dl;
+        if next_layer >= 0 && next_layer < layers {
+            adjacent.push((next_layer, x, y));
+        }
+    }
+    adjacent
+}
+
+/// [`grid_adjacent`], filtered down to nodes `state` hasn't marked
+/// [`PointState::Blocked`].
+fn open_neighbors(
+    state: &RoutingState<impl atoll::grid::AtollLayer>,
+    node: NodeId,
+    shape: (i64, i64, i64),
+) -> Vec<NodeId> {
+    grid_adjacent(node, shape)
+        .into_iter()
+        .filter(|&next| !matches!(state.point(next), PointState::Blocked))
+        .collect()
+}
+
+/// Per-node cost state maintained across PathFinder iterations.
+#[derive(Clone, Copy, Debug)]
+struct NodeCost {
+    base_cost: f64,
+    present_cost: f64,
+    history_cost: f64,
+    occupancy: usize,
+}
+
+impl NodeCost {
+    fn new(base_cost: f64) -> Self {
+        Self {
+            base_cost,
+            present_cost: 1.0,
+            history_cost: 0.0,
+            occupancy: 0,
+        }
+    }
+
+    /// The cost PathFinder assigns to routing through this node:
+    /// `(base_cost + history_cost) * present_cost`.
+    fn cost(&self) -> f64 {
+        (self.base_cost + self.history_cost) * self.present_cost
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+struct HeapEntry {
+    cost: f64,
+    node: NodeId,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reverse for a min-heap: `BinaryHeap` is a max-heap by default.
+        other
+            .cost
+            .partial_cmp(&self.cost)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A single net queued for routing.
+struct PendingNet {
+    /// Position in the original `to_connect` order, kept stable across
+    /// rip-up-and-reroute iterations so routing is deterministic.
+    idx: usize,
+    /// Whether this net may legally share nodes with other nets once
+    /// routed (e.g. `vdd`/`vss`/`clock`, which fan out to many taps/gates).
+    shared: bool,
+    terminals: Vec<NodeId>,
+}
+
+/// Per-node occupancy tracked by [`LeeMazeRouter`] as nets are routed,
+/// mirroring [`PathFinderRouter`]'s capacity model: a node touched by a
+/// shared net (`vdd`/`vss`/`clock`) gets capacity 4 so those high-fanout
+/// nets can all terminate there, while every other node has capacity 1.
+#[derive(Clone, Copy, Debug, Default)]
+struct NodeOccupancy {
+    count: usize,
+    shared: bool,
+}
+
+impl NodeOccupancy {
+    fn capacity(&self) -> usize {
+        if self.shared {
+            4
+        } else {
+            1
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.count >= self.capacity()
+    }
+}
+
+/// A negotiated-congestion router, in the style of the PathFinder FPGA
+/// place-and-route algorithm.
+///
+/// Each net is routed independently as a shortest path where a node's edge
+/// cost is `(base_cost + history_cost) * present_cost`. After every net has
+/// been routed in a pass, nodes used by more nets than their capacity allows
+/// have their `present_cost` multiplied by [`Self::present_cost_growth`] and
+/// their `history_cost` incremented by their overuse times
+/// [`Self::history_cost_step`]. Routing repeats until no node is overused or
+/// [`Self::max_iterations`] passes have run. `vdd`/`vss`/`clock` are treated
+/// as shared nets that may legally terminate on the same node as other
+/// shared nets without being considered overused.
+#[derive(Clone, Copy, Debug)]
+pub struct PathFinderRouter {
+    present_cost_growth: f64,
+    history_cost_step: f64,
+    max_iterations: usize,
+}
+
+impl Default for PathFinderRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PathFinderRouter {
+    /// Creates a [`PathFinderRouter`] with the default cost growth schedule
+    /// and iteration cap.
+    pub fn new() -> Self {
+        Self {
+            present_cost_growth: DEFAULT_PRESENT_COST_GROWTH,
+            history_cost_step: DEFAULT_HISTORY_COST_STEP,
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Sets the factor `present_cost` is multiplied by for each iteration a
+    /// node remains overused.
+    pub fn with_present_cost_growth(mut self, factor: f64) -> Self {
+        self.present_cost_growth = factor;
+        self
+    }
+
+    /// Sets the amount added to `history_cost` per unit of overuse observed
+    /// in an iteration.
+    pub fn with_history_cost_step(mut self, step: f64) -> Self {
+        self.history_cost_step = step;
+        self
+    }
+
+    /// Sets the maximum number of rip-up-and-reroute iterations.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Finds the lowest-cost path connecting `terminals` through `costs`,
+    /// given the node adjacency exposed by `neighbors`.
+    fn shortest_path(
+        terminals: &[NodeId],
+        costs: &HashMap<NodeId, NodeCost>,
+        neighbors: impl Fn(NodeId) -> Vec<NodeId>,
+    ) -> Vec<NodeId> {
+        // Multi-terminal nets are routed as a minimum-cost tree by growing a
+        // Dijkstra search from the first terminal and greedily attaching
+        // each remaining terminal to the closest node already in the tree.
+        let mut tree = vec![terminals[0]];
+        let mut visited_path: HashMap<NodeId, NodeId> = HashMap::new();
+        for &target in &terminals[1..] {
+            let mut dist: HashMap<NodeId, f64> = tree.iter().map(|&n| (n, 0.0)).collect();
+            let mut prev: HashMap<NodeId, NodeId> = HashMap::new();
+            let mut heap: BinaryHeap<HeapEntry> = tree
+                .iter()
+                .map(|&node| HeapEntry { cost: 0.0, node })
+                .collect();
+            let mut reached = None;
+            while let Some(HeapEntry { cost, node }) = heap.pop() {
+                if node == target {
+                    reached = Some(node);
+                    break;
+                }
+                if cost > *dist.get(&node).unwrap_or(&f64::INFINITY) {
+                    continue;
+                }
+                for next in neighbors(node) {
+                    // A node absent from `costs` hasn't been used by any
+                    // net yet, so it's priced at its base cost (1.0), not
+                    // infinity -- infinity is reserved for nodes `neighbors`
+                    // itself excludes (i.e. actually blocked), and defaulting
+                    // unseen nodes to infinity here would make every
+                    // neighbor of the seed terminal(s) unreachable on the
+                    // first expansion.
+                    let edge_cost = costs
+                        .get(&next)
+                        .map(NodeCost::cost)
+                        .unwrap_or(1.0);
+                    let next_cost = cost + edge_cost;
+                    if next_cost < *dist.get(&next).unwrap_or(&f64::INFINITY) {
+                        dist.insert(next, next_cost);
+                        prev.insert(next, node);
+                        heap.push(HeapEntry {
+                            cost: next_cost,
+                            node: next,
+                        });
+                    }
+                }
+            }
+            if reached.is_some() {
+                let mut node = target;
+                while let Some(&p) = prev.get(&node) {
+                    visited_path.insert(node, p);
+                    if !tree.contains(&node) {
+                        tree.push(node);
+                    }
+                    node = p;
+                }
+            }
+        }
+        tree
+    }
+}
+
+impl Router for PathFinderRouter {
+    fn route(
+        &self,
+        state: RoutingState<impl atoll::grid::AtollLayer>,
+        to_connect: Vec<Vec<PointState>>,
+    ) -> RoutingState<impl atoll::grid::AtollLayer> {
+        let mut state = state;
+        let shape = state.shape();
+        let nodes = grid_nodes(shape);
+
+        let nets: Vec<PendingNet> = to_connect
+            .iter()
+            .enumerate()
+            .map(|(idx, points)| {
+                let terminals = terminals_for_net(points, &nodes, idx);
+                let shared = terminals.len() > SHARED_NET_TERMINAL_THRESHOLD;
+                PendingNet {
+                    idx,
+                    shared,
+                    terminals,
+                }
+            })
+            .collect();
+
+        let mut costs: HashMap<NodeId, NodeCost> = HashMap::new();
+        let mut paths: Vec<Vec<NodeId>> = vec![Vec::new(); nets.len()];
+        let mut iteration = 0;
+        loop {
+            let mut occupancy: HashMap<NodeId, usize> = HashMap::new();
+            // Whether any net occupying a given node is shared, so the
+            // node's capacity reflects the net actually routed through it
+            // rather than a single shared-net-anywhere blanket value.
+            let mut node_shared: HashMap<NodeId, bool> = HashMap::new();
+            for net in &nets {
+                if net.terminals.len() < 2 {
+                    continue;
+                }
+                let path = Self::shortest_path(&net.terminals, &costs, |node| {
+                    open_neighbors(&state, node, shape)
+                });
+                for &node in &path {
+                    *occupancy.entry(node).or_insert(0) += 1;
+                    costs.entry(node).or_insert_with(|| NodeCost::new(1.0));
+                    let node_is_shared = node_shared.entry(node).or_insert(false);
+                    *node_is_shared |= net.shared;
+                }
+                paths[net.idx] = path;
+            }
+
+            let mut any_overused = false;
+            for (node, count) in &occupancy {
+                let capacity = if node_shared.get(node).copied().unwrap_or(false) {
+                    4
+                } else {
+                    1
+                };
+                if *count > capacity {
+                    any_overused = true;
+                    let entry = costs.entry(*node).or_insert_with(|| NodeCost::new(1.0));
+                    entry.occupancy = *count;
+                    entry.present_cost *= self.present_cost_growth;
+                    entry.history_cost += (*count - capacity) as f64 * self.history_cost_step;
+                }
+            }
+
+            iteration += 1;
+            if !any_overused || iteration >= self.max_iterations {
+                break;
+            }
+        }
+
+        for (net, path) in nets.iter().zip(paths.iter()) {
+            for &node in path {
+                state.set_point(node, PointState::Routed { net: net.idx });
+            }
+        }
+
+        state
+    }
+}
+
+#[test]
+fn node_cost_applies_present_and_history_cost() {
+    let mut cost = NodeCost::new(1.0);
+    assert_eq!(cost.cost(), 1.0);
+    cost.present_cost *= 2.0;
+    cost.history_cost += 1.0;
+    assert_eq!(cost.cost(), 4.0);
+}
+
+#[test]
+fn shortest_path_connects_two_terminals_through_open_neighbors() {
+    // A 1x4 strip on a single layer: (0,0,0) - (0,1,0) - (0,2,0) - (0,3,0).
+    let neighbors = |node: NodeId| -> Vec<NodeId> {
+        let (layer, x, _y) = node;
+        let mut out = Vec::new();
+        if x > 0 {
+            out.push((layer, x - 1, 0));
+        }
+        if x < 3 {
+            out.push((layer, x + 1, 0));
+        }
+        out
+    };
+    let costs: HashMap<NodeId, NodeCost> = HashMap::new();
+    let path = PathFinderRouter::shortest_path(&[(0, 0, 0), (0, 3, 0)], &costs, neighbors);
+    assert_eq!(
+        path.into_iter().collect::<std::collections::HashSet<_>>(),
+        [(0, 0, 0), (0, 1, 0), (0, 2, 0), (0, 3, 0)]
+            .into_iter()
+            .collect()
+    );
+}
+
+#[test]
+fn shortest_path_prefers_the_cheaper_of_two_routes() {
+    // Two parallel routes from (0,0,0) to (0,2,0): two hops through (0,1,0)
+    // on layer 0, or three hops via (1,0,0)/(1,1,0) on layer 1. Layer 1 is made
+    // expensive so the search should stay on layer 0.
+    let neighbors = |node: NodeId| -> Vec<NodeId> {
+        match node {
+            (0, 0, 0) => vec![(0, 1, 0), (1, 0, 0)],
+            (0, 1, 0) => vec![(0, 0, 0), (0, 2, 0)],
+            (1, 0, 0) => vec![(0, 0, 0), (1, 1, 0)],
+            (1, 1, 0) => vec![(1, 0, 0), (0, 2, 0)],
+            (0, 2, 0) => vec![(0, 1, 0), (1, 1, 0)],
+            _ => vec![],
+        }
+    };
+    let mut costs: HashMap<NodeId, NodeCost> = HashMap::new();
+    costs.insert((0, 1, 0), NodeCost::new(1.0));
+    costs.insert((1, 0, 0), NodeCost::new(10.0));
+    costs.insert((1, 1, 0), NodeCost::new(10.0));
+    let path = PathFinderRouter::shortest_path(&[(0, 0, 0), (0, 2, 0)], &costs, neighbors);
+    assert!(path.contains(&(0, 1, 0)));
+    assert!(!path.contains(&(1, 0, 0)));
+}
+
+#[test]
+fn grid_adjacent_drops_out_of_bounds_steps_and_includes_vias() {
+    let shape = (2, 2, 2);
+    let mut neighbors = grid_adjacent((0, 0, 0), shape);
+    neighbors.sort();
+    assert_eq!(neighbors, vec![(0, 0, 1), (0, 1, 0), (1, 0, 0)]);
+}
+
+#[test]
+fn terminals_for_net_extracts_only_the_matching_net() {
+    let nodes = grid_nodes((1, 1, 3));
+    let points = vec![
+        PointState::Routed { net: 0 },
+        PointState::Available,
+        PointState::Routed { net: 0 },
+    ];
+    let terminals = terminals_for_net(&points, &nodes, 0);
+    assert_eq!(terminals, vec![(0, 0, 0), (0, 0, 2)]);
+}
+
+/// A maze router based on Lee's algorithm.
+///
+/// Each net is routed independently with a BFS wavefront expansion from its
+/// first terminal: every reachable grid node is marked with the minimum
+/// number of hops needed to reach it, respecting nodes blocked by
+/// already-drawn devices and via-legality between adjacent layers, and the
+/// path to each remaining terminal is recovered by backtracing from it along
+/// strictly-decreasing cost back to the wavefront's source. Nets are routed
+/// in order of increasing bounding-box area, since a net with little room to
+/// route around congestion should get first claim on contested cells. When a
+/// net comes up unroutable, the already-routed net with the largest bounding
+/// box is ripped up and requeued, and the failed net is retried.
+#[derive(Clone, Copy, Debug)]
+pub struct LeeMazeRouter {
+    max_iterations: usize,
+}
+
+impl Default for LeeMazeRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LeeMazeRouter {
+    /// Creates a [`LeeMazeRouter`] with the default rip-up-and-retry
+    /// iteration cap.
+    pub fn new() -> Self {
+        Self {
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Sets the maximum number of rip-ups over the whole routing run. Once
+    /// it is exceeded, a net that comes up unroutable is left unrouted.
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Runs a BFS wavefront from `terminals[0]`, marking every reachable
+    /// node's minimum hop count, then backtraces from each remaining
+    /// terminal by always stepping to an unblocked neighbor with strictly
+    /// lower cost until the source is reached. Returns `None` if any
+    /// terminal is unreachable or a backtrace gets stuck without a
+    /// strictly-decreasing neighbor to step to.
+    fn maze_route(
+        terminals: &[NodeId],
+        occupancy: &HashMap<NodeId, NodeOccupancy>,
+        neighbors: impl Fn(NodeId) -> Vec<NodeId>,
+    ) -> Option<Vec<NodeId>> {
+        let is_full = |node: &NodeId| occupancy.get(node).is_some_and(NodeOccupancy::is_full);
+        let source = terminals[0];
+        let mut cost: HashMap<NodeId, u32> = HashMap::new();
+        cost.insert(source, 0);
+        let mut frontier = std::collections::VecDeque::new();
+        frontier.push_back(source);
+        while let Some(node) = frontier.pop_front() {
+            let d = cost[&node];
+            for next in neighbors(node) {
+                if is_full(&next) {
+                    continue;
+                }
+                if cost.contains_key(&next) {
+                    continue;
+                }
+                cost.insert(next, d + 1);
+                frontier.push_back(next);
+            }
+        }
+
+        let mut path = vec![source];
+        for &target in &terminals[1..] {
+            let mut node = target;
+            let mut node_cost = *cost.get(&node)?;
+            let mut leg = vec![node];
+            while node != source {
+                let next = neighbors(node)
+                    .into_iter()
+                    .filter(|n| !is_full(n))
+                    .filter_map(|n| cost.get(&n).map(|&c| (n, c)))
+                    .filter(|&(_, c)| c < node_cost)
+                    .min_by_key(|&(_, c)| c)?;
+                node = next.0;
+                node_cost = next.1;
+                leg.push(node);
+            }
+            // A leg's backtrace runs all the way to `source`, so it can
+            // retrace nodes already in `path` (including `source` itself);
+            // push only nodes not already present -- otherwise a
+            // multi-terminal net's occupancy gets inflated once per leg
+            // that shares a node with the trunk.
+            for node in leg {
+                if !path.contains(&node) {
+                    path.push(node);
+                }
+            }
+        }
+        Some(path)
+    }
+}
+
+impl Router for LeeMazeRouter {
+    fn route(
+        &self,
+        state: RoutingState<impl atoll::grid::AtollLayer>,
+        to_connect: Vec<Vec<PointState>>,
+    ) -> RoutingState<impl atoll::grid::AtollLayer> {
+        let mut state = state;
+        let shape = state.shape();
+        let nodes = grid_nodes(shape);
+
+        let nets: Vec<PendingNet> = to_connect
+            .iter()
+            .enumerate()
+            .map(|(idx, points)| {
+                let terminals = terminals_for_net(points, &nodes, idx);
+                let shared = terminals.len() > SHARED_NET_TERMINAL_THRESHOLD;
+                PendingNet {
+                    idx,
+                    shared,
+                    terminals,
+                }
+            })
+            .collect();
+
+        // Smallest bounding-box area first, so a net with little room to
+        // route around congestion gets first claim on contested cells.
+        let mut queue: std::collections::VecDeque<usize> = (0..nets.len()).collect();
+        queue
+            .make_contiguous()
+            .sort_by_key(|&i| bounding_box_area(&nets[i].terminals));
+
+        let mut occupancy: HashMap<NodeId, NodeOccupancy> = HashMap::new();
+        let mut routed: HashMap<usize, Vec<NodeId>> = HashMap::new();
+        let mut rip_ups = 0;
+        while let Some(i) = queue.pop_front() {
+            let net = &nets[i];
+            if net.terminals.len() < 2 {
+                continue;
+            }
+            if let Some(path) = Self::maze_route(&net.terminals, &occupancy, |node| {
+                open_neighbors(&state, node, shape)
+            }) {
+                for &node in &path {
+                    let entry = occupancy.entry(node).or_default();
+                    entry.count += 1;
+                    entry.shared |= net.shared;
+                }
+                routed.insert(i, path);
+                continue;
+            }
+
+            // Unroutable with the current blockages: rip up whichever
+            // already-routed net has the largest bounding box (it has the
+            // most slack to find another path later), free its nodes, and
+            // retry this net.
+            rip_ups += 1;
+            if rip_ups > self.max_iterations {
+                continue;
+            }
+            if let Some(&victim) = routed
+                .keys()
+                .max_by_key(|&&j| bounding_box_area(&nets[j].terminals))
+            {
+                for node in routed.remove(&victim).unwrap() {
+                    if let Some(entry) = occupancy.get_mut(&node) {
+                        entry.count = entry.count.saturating_sub(1);
+                        if entry.count == 0 {
+                            occupancy.remove(&node);
+                        }
+                    }
+                }
+                queue.push_back(victim);
+                queue.push_front(i);
+            }
+        }
+
+        for (&i, path) in &routed {
+            for &node in path {
+                state.set_point(node, PointState::Routed { net: nets[i].idx });
+            }
+        }
+
+        state
+    }
+}
+
+#[test]
+fn maze_route_finds_a_path_around_a_blocked_node() {
+    // 3x1 strip: (0,0,0) - (0,1,0) - (0,2,0), with the direct hop blocked
+    // so the only way through is via the layer-1 node above (0,1,0).
+    let neighbors = |node: NodeId| -> Vec<NodeId> {
+        match node {
+            (0, 0, 0) => vec![(0, 1, 0), (1, 0, 0)],
+            (0, 1, 0) => vec![(0, 0, 0), (0, 2, 0), (1, 1, 0)],
+            (0, 2, 0) => vec![(0, 1, 0), (1, 2, 0)],
+            (1, 0, 0) => vec![(0, 0, 0), (1, 1, 0)],
+            (1, 1, 0) => vec![(1, 0, 0), (1, 2, 0), (0, 1, 0)],
+            (1, 2, 0) => vec![(1, 1, 0), (0, 2, 0)],
+            _ => vec![],
+        }
+    };
+    let mut occupancy = HashMap::new();
+    occupancy.insert(
+        (0, 1, 0),
+        NodeOccupancy {
+            count: 1,
+            shared: false,
+        },
+    );
+    let path = LeeMazeRouter::maze_route(&[(0, 0, 0), (0, 2, 0)], &occupancy, neighbors)
+        .expect("a path should exist via layer 1");
+    assert!(!path.contains(&(0, 1, 0)));
+    assert!(path.contains(&(1, 1, 0)));
+}
+
+#[test]
+fn maze_route_returns_none_when_a_terminal_is_unreachable() {
+    let neighbors = |_node: NodeId| -> Vec<NodeId> { Vec::new() };
+    let occupancy = HashMap::new();
+    assert!(LeeMazeRouter::maze_route(&[(0, 0, 0), (0, 5, 5)], &occupancy, neighbors).is_none());
+}
+
+#[test]
+fn maze_route_lets_a_shared_node_host_more_than_one_net() {
+    // A single node between the two terminals; since it's marked shared
+    // with one occupant already, a second net should still be able to
+    // route through it (capacity 4), unlike a non-shared node at capacity.
+    let neighbors = |node: NodeId| -> Vec<NodeId> {
+        match node {
+            (0, 0, 0) => vec![(0, 1, 0)],
+            (0, 1, 0) => vec![(0, 0, 0), (0, 2, 0)],
+            (0, 2, 0) => vec![(0, 1, 0)],
+            _ => vec![],
+        }
+    };
+    let mut occupancy = HashMap::new();
+    occupancy.insert(
+        (0, 1, 0),
+        NodeOccupancy {
+            count: 1,
+            shared: true,
+        },
+    );
+    let path = LeeMazeRouter::maze_route(&[(0, 0, 0), (0, 2, 0)], &occupancy, neighbors)
+        .expect("a shared node under capacity should still be usable");
+    assert!(path.contains(&(0, 1, 0)));
+}
+
+#[test]
+fn bounding_box_area_covers_all_terminals() {
+    assert_eq!(bounding_box_area(&[(0, 0, 0), (0, 3, 1), (0, 1, 4)]), 4 * 5);
+    assert_eq!(bounding_box_area(&[]), 0);
+}
+
+/// The area of the smallest axis-aligned bounding box (in LCM-grid units)
+/// enclosing `terminals`, ignoring layer.
+fn bounding_box_area(terminals: &[NodeId]) -> i64 {
+    let Some((&(_, x0, y0), rest)) = terminals.split_first() else {
+        return 0;
+    };
+    let (mut min_x, mut max_x) = (x0, x0);
+    let (mut min_y, mut max_y) = (y0, y0);
+    for &(_, x, y) in rest {
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        min_y = min_y.min(y);
+        max_y = max_y.max(y);
+    }
+    (max_x - min_x + 1) * (max_y - min_y + 1)
+}
@@ -0,0 +1,735 @@
+//! Transient testbenches for characterizing [`StrongArm`](crate::strongarm::StrongArm) instances.
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+use atoll::TileWrapper;
+use ngspice::Ngspice;
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use spectre::analysis::tran::Tran;
+use spectre::Spectre;
+use substrate::block::Block;
+use substrate::io::{Signal, TestbenchIo};
+use substrate::pdk::corner::{InstallCorner, Pvt};
+use substrate::pdk::Pdk;
+use substrate::schematic::schema::Schema;
+use substrate::schematic::{CellBuilder, ExportsNestedData, NestedData, Schematic};
+use substrate::simulation::{SimulationContext, Testbench};
+
+use crate::strongarm::{StrongArm, StrongArmImpl};
+
+/// The simulated transient window, in seconds.
+const TRAN_STOP: Decimal = dec!(5e-9);
+/// The time at which the comparator's clock rises.
+const CLK_EDGE: Decimal = dec!(1e-9);
+/// Fraction of VDD used as the valid-logic-level threshold for the outputs.
+const VOH_FRACTION: Decimal = dec!(0.5);
+/// Half-width, as a fraction of VDD, of the differential band the two
+/// outputs must clear before the comparator is considered to have resolved.
+const METASTABLE_BAND_FRACTION: Decimal = dec!(0.1);
+/// Default time after the clock edge after which a comparator still inside
+/// the metastable band is reported as [`ComparatorDecision::Metastable`].
+const DEFAULT_METASTABLE_TIMEOUT: Decimal = dec!(2e-9);
+/// First time offset after the clock edge at which the output split is
+/// sampled for the regeneration time constant fit.
+const REGEN_TAU_SAMPLE_T0: Decimal = dec!(50e-12);
+/// Second time offset after the clock edge at which the output split is
+/// sampled for the regeneration time constant fit.
+const REGEN_TAU_SAMPLE_T1: Decimal = dec!(150e-12);
+
+/// The analog simulator backend used to run a [`StrongArmTranTb`].
+///
+/// Both backends drive the same schematic and read out the same
+/// [`ComparatorDecision`]; this only selects which simulator is dispatched to
+/// by [`Testbench::run`].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum SimBackend {
+    /// Cadence Spectre.
+    Spectre,
+    /// Open-source Ngspice.
+    Ngspice,
+}
+
+/// The decision produced by a StrongARM comparator at the end of an evaluate phase.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ComparatorDecision {
+    /// `output.p` resolved high and `output.n` resolved low.
+    Pos,
+    /// `output.n` resolved high and `output.p` resolved low.
+    Neg,
+    /// Neither output reached a valid logic level within the simulation
+    /// window, or the outputs stayed within the metastable band for longer
+    /// than the configured timeout. Typical of small differential inputs.
+    Metastable,
+}
+
+/// The result of clocking a [`StrongArmTranTb`] once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StrongArmTranResult {
+    /// The comparator's decision.
+    pub decision: ComparatorDecision,
+    /// Delay from the clock edge to the first output reaching a valid logic
+    /// level. `None` when `decision` is [`ComparatorDecision::Metastable`].
+    pub regen_time: Option<Decimal>,
+    /// The regeneration time constant `tau` fit from two samples of
+    /// `|output.p - output.n|` shortly after the clock edge, under the
+    /// assumption that the split grows as `exp(t / tau)`. `None` if either
+    /// sample falls outside the simulated window, or if the split did not
+    /// grow between the two samples (e.g. the comparator had already railed
+    /// by [`REGEN_TAU_SAMPLE_T0`]).
+    pub regen_tau: Option<Decimal>,
+}
+
+/// A post-layout, PEX-extracted netlist to simulate against instead of a
+/// schematic-level [`StrongArm`].
+///
+/// Produced by running parasitic extraction on a written-out layout; see
+/// `strongarm_pex` in the `strongarm1` crate for the flow that builds one of
+/// these.
+#[derive(Serialize, Deserialize, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ExtractedDut {
+    /// Path to the PEX-extracted SPICE netlist.
+    pub netlist: std::path::PathBuf,
+    /// Name of the top-level subcircuit inside `netlist` to instantiate.
+    pub cell_name: substrate::arcstr::ArcStr,
+}
+
+/// Selects whether a [`StrongArmTranTb`] simulates the schematic-level
+/// [`StrongArm`] generator output or a post-layout [`ExtractedDut`].
+#[derive_where::derive_where(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub enum Dut<T> {
+    /// Simulate the schematic produced directly by the [`StrongArm`] generator.
+    Schematic(TileWrapper<StrongArm<T>>),
+    /// Simulate a PEX-extracted netlist taken from the generator's layout.
+    Extracted(ExtractedDut, #[serde(bound(deserialize = ""))] PhantomData<fn() -> T>),
+}
+
+impl<T> Dut<T> {
+    /// Wraps a PEX-extracted netlist as a [`Dut::Extracted`].
+    pub fn extracted(extracted: ExtractedDut) -> Self {
+        Self::Extracted(extracted, PhantomData)
+    }
+}
+
+/// A transient testbench that clocks a [`StrongArm`] once and reports its
+/// [`StrongArmTranResult`].
+#[derive_where::derive_where(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Serialize, Deserialize)]
+pub struct StrongArmTranTb<T, C> {
+    dut: Dut<T>,
+    vinp: Decimal,
+    vinn: Decimal,
+    input_n: bool,
+    pvt: Pvt<C>,
+    backend: SimBackend,
+    /// Seeds the PDK's statistical mismatch model for a Monte Carlo sample;
+    /// `None` runs a nominal (mismatch-free) simulation.
+    mc_seed: Option<u64>,
+    /// Time after the clock edge after which a comparator still inside the
+    /// metastable band is reported as [`ComparatorDecision::Metastable`].
+    metastable_timeout: Decimal,
+}
+
+impl<T, C> StrongArmTranTb<T, C> {
+    /// Creates a new [`StrongArmTranTb`] that clocks the schematic-level `dut`
+    /// once with the given differential input and reports the resulting
+    /// [`ComparatorDecision`], dispatching to the given [`SimBackend`].
+    pub fn new(
+        dut: TileWrapper<StrongArm<T>>,
+        vinp: Decimal,
+        vinn: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+    ) -> Self {
+        Self::with_dut(Dut::Schematic(dut), vinp, vinn, input_n, pvt, backend)
+    }
+
+    /// Creates a new [`StrongArmTranTb`] that clocks a post-layout, PEX-extracted
+    /// netlist instead of the schematic-level generator output.
+    pub fn new_extracted(
+        dut: ExtractedDut,
+        vinp: Decimal,
+        vinn: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+    ) -> Self {
+        Self::with_dut(Dut::extracted(dut), vinp, vinn, input_n, pvt, backend)
+    }
+
+    /// Creates a new [`StrongArmTranTb`] simulating either view of the DUT
+    /// selected by `dut`.
+    pub fn with_dut(
+        dut: Dut<T>,
+        vinp: Decimal,
+        vinn: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+    ) -> Self {
+        Self {
+            dut,
+            vinp,
+            vinn,
+            input_n,
+            pvt,
+            backend,
+            mc_seed: None,
+            metastable_timeout: DEFAULT_METASTABLE_TIMEOUT,
+        }
+    }
+
+    /// Runs this testbench with the PDK's statistical mismatch model seeded
+    /// by `seed`, for use in a Monte Carlo sweep.
+    pub fn with_mc_seed(mut self, seed: u64) -> Self {
+        self.mc_seed = Some(seed);
+        self
+    }
+
+    /// Overrides the default metastability timeout (time after the clock
+    /// edge after which a comparator still inside the metastable band is
+    /// reported as [`ComparatorDecision::Metastable`]).
+    pub fn with_metastable_timeout(mut self, timeout: Decimal) -> Self {
+        self.metastable_timeout = timeout;
+        self
+    }
+}
+
+impl<T: Any, C: Any> Block for StrongArmTranTb<T, C> {
+    type Io = TestbenchIo;
+
+    fn id() -> substrate::arcstr::ArcStr {
+        substrate::arcstr::literal!("strong_arm_tran_tb")
+    }
+
+    fn name(&self) -> substrate::arcstr::ArcStr {
+        substrate::arcstr::literal!("strong_arm_tran_tb")
+    }
+
+    fn io(&self) -> Self::Io {
+        Default::default()
+    }
+}
+
+impl<T: Any, C: Any> ExportsNestedData for StrongArmTranTb<T, C> {
+    type NestedData = NestedData;
+}
+
+impl<PDK, T, C> Schematic<PDK> for StrongArmTranTb<T, C>
+where
+    PDK: Pdk + Schema,
+    T: StrongArmImpl<PDK> + Any,
+    C: Any,
+{
+    fn schematic(
+        &self,
+        io: &substrate::io::schematic::Bundle<TestbenchIo>,
+        cell: &mut CellBuilder<PDK>,
+    ) -> substrate::error::Result<Self::NestedData> {
+        let vdd = cell.signal("vdd", Signal);
+        let vss = io.vss;
+        let vinp = cell.signal("vinp", Signal);
+        let vinn = cell.signal("vinn", Signal);
+        let clock = cell.signal("clock", Signal);
+
+        let dut = match &self.dut {
+            Dut::Schematic(dut) => cell.instantiate(*dut),
+            // Blackbox the PEX-extracted netlist behind the same
+            // `ClockedDiffComparatorIo` pins as the schematic generator, so
+            // the rest of this testbench is unchanged between the two views.
+            Dut::Extracted(extracted, _) => cell.instantiate(spice::blocks::RawInstance::with_ports(
+                extracted.cell_name.clone(),
+                extracted.netlist.clone(),
+                crate::strongarm::ClockedDiffComparatorIo::default(),
+            )),
+        };
+        cell.connect(dut.io().vdd, vdd);
+        cell.connect(dut.io().vss, vss);
+        cell.connect(dut.io().clock, clock);
+        cell.connect(dut.io().input.p, vinp);
+        cell.connect(dut.io().input.n, vinn);
+
+        if let Some(seed) = self.mc_seed {
+            // Seeds the PDK's per-device statistical mismatch model so that
+            // each Monte Carlo sample draws an independent set of device
+            // variations.
+            cell.instantiate_tb(spice::blocks::RawSpice::new(format!(
+                ".param mc_mismatch_seed={seed}"
+            )));
+        }
+
+        cell.instantiate_tb(spice::blocks::Vsource::dc(self.pvt.voltage), vdd, vss);
+        cell.instantiate_tb(spice::blocks::Vsource::dc(self.vinp), vinp, vss);
+        cell.instantiate_tb(spice::blocks::Vsource::dc(self.vinn), vinn, vss);
+        cell.instantiate_tb(
+            spice::blocks::Vsource::pulse(spice::blocks::Pulse {
+                val0: dec!(0),
+                val1: self.pvt.voltage,
+                delay: Some(CLK_EDGE),
+                ..Default::default()
+            }),
+            clock,
+            vss,
+        );
+
+        Ok(NestedData::from_iter([
+            ("output_p", dut.io().output.p),
+            ("output_n", dut.io().output.n),
+            ("clock", clock),
+        ]))
+    }
+}
+
+/// Turns the sampled output waveforms into a [`StrongArmTranResult`].
+///
+/// Walks the waveform forward from `clk_edge` looking for the first sample
+/// where one output crosses the valid-logic threshold while the other does
+/// not. If the two outputs are still within the metastable band after
+/// `metastable_timeout` has elapsed (or the window ends before either output
+/// crosses), the comparator is reported as [`ComparatorDecision::Metastable`].
+fn decide(
+    time: &[Decimal],
+    output_p: &[Decimal],
+    output_n: &[Decimal],
+    vdd: Decimal,
+    clk_edge: Decimal,
+    metastable_timeout: Decimal,
+) -> StrongArmTranResult {
+    let threshold = vdd * VOH_FRACTION;
+    let band = vdd * METASTABLE_BAND_FRACTION;
+
+    for ((&t, &p), &n) in time.iter().zip(output_p).zip(output_n) {
+        if t < clk_edge {
+            continue;
+        }
+
+        let p_high = p > threshold;
+        let n_high = n > threshold;
+        let decision = match (p_high, n_high) {
+            (true, false) => Some(ComparatorDecision::Pos),
+            (false, true) => Some(ComparatorDecision::Neg),
+            _ => None,
+        };
+        if let Some(decision) = decision {
+            return StrongArmTranResult {
+                decision,
+                regen_time: Some(t - clk_edge),
+                regen_tau: regen_tau(time, output_p, output_n, clk_edge),
+            };
+        }
+
+        if (p - n).abs() < band && t - clk_edge > metastable_timeout {
+            break;
+        }
+    }
+
+    StrongArmTranResult {
+        decision: ComparatorDecision::Metastable,
+        regen_time: None,
+        regen_tau: regen_tau(time, output_p, output_n, clk_edge),
+    }
+}
+
+/// Samples `|output_p - output_n|` at `clk_edge + REGEN_TAU_SAMPLE_T0` and
+/// `clk_edge + REGEN_TAU_SAMPLE_T1` and fits the exponential regeneration
+/// time constant `tau` such that the split grows as `exp((t - t0) / tau)`
+/// between the two samples.
+///
+/// Returns `None` if either sample falls outside the simulated window, or if
+/// the split did not grow between the two samples.
+fn regen_tau(
+    time: &[Decimal],
+    output_p: &[Decimal],
+    output_n: &[Decimal],
+    clk_edge: Decimal,
+) -> Option<Decimal> {
+    let sample_at = |t: Decimal| -> Option<Decimal> {
+        let idx = time.iter().position(|&ti| ti >= t)?;
+        Some((output_p[idx] - output_n[idx]).abs())
+    };
+
+    let t0 = clk_edge + REGEN_TAU_SAMPLE_T0;
+    let t1 = clk_edge + REGEN_TAU_SAMPLE_T1;
+    let split0 = sample_at(t0)?;
+    let split1 = sample_at(t1)?;
+
+    if split0 <= dec!(0) || split1 <= split0 {
+        return None;
+    }
+
+    let ln_ratio = (split1 / split0).checked_ln()?;
+    (t1 - t0).checked_div(ln_ratio)
+}
+
+impl<PDK, T, C> Testbench<PDK> for StrongArmTranTb<T, C>
+where
+    PDK: Pdk + Schema + InstallCorner<C>,
+    T: StrongArmImpl<PDK> + Any,
+    C: Any + Copy,
+{
+    type Output = StrongArmTranResult;
+
+    fn run(&self, sim: SimulationContext<PDK>) -> Self::Output {
+        let analysis = Tran {
+            stop: TRAN_STOP,
+            ..Default::default()
+        };
+
+        let output = match self.backend {
+            SimBackend::Spectre => sim
+                .simulate::<Spectre, _>(self.pvt.corner, analysis)
+                .expect("failed to run StrongARM transient simulation"),
+            SimBackend::Ngspice => sim
+                .simulate::<Ngspice, _>(self.pvt.corner, analysis)
+                .expect("failed to run StrongARM transient simulation"),
+        };
+
+        decide(
+            &output.time,
+            &output.output_p,
+            &output.output_n,
+            self.pvt.voltage,
+            CLK_EDGE,
+            self.metastable_timeout,
+        )
+    }
+}
+
+/// The error bracket used while bisecting for the input-referred offset of a
+/// [`StrongArm`] comparator.
+///
+/// `lo` is known to produce [`ComparatorDecision::Neg`] and `hi` is known to
+/// produce [`ComparatorDecision::Pos`].
+#[derive(Clone, Copy, Debug)]
+struct OffsetBracket {
+    lo: Decimal,
+    hi: Decimal,
+}
+
+/// The result of an [`offset`](StrongArmTranTb::offset) measurement.
+pub type OffsetResult = Option<Decimal>;
+
+impl<T: Any + Clone, C: Any + Clone> StrongArmTranTb<T, C> {
+    /// Measures the input-referred offset voltage of `dut` at the given
+    /// common-mode voltage and PVT corner.
+    ///
+    /// Performs a bisection on `vdiff = vinp - vinn`, starting from a
+    /// `[-vdd/2, vdd/2]` bracket, running the transient testbench at the
+    /// midpoint and narrowing the bracket based on the returned
+    /// [`ComparatorDecision`]. Stops once the bracket width is below
+    /// `resolution` or `max_iters` bisections have run. Returns `None` if
+    /// either endpoint of the initial bracket fails to rail, or if any
+    /// bisection probe comes back [`ComparatorDecision::Metastable`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn offset<PDK>(
+        ctx: &substrate::context::PdkContext<PDK>,
+        dut: Dut<T>,
+        vcm: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+        vdd: Decimal,
+        resolution: Decimal,
+        max_iters: usize,
+        work_dir: impl AsRef<std::path::Path>,
+    ) -> substrate::error::Result<OffsetResult>
+    where
+        PDK: Pdk + Schema + InstallCorner<C>,
+        T: StrongArmImpl<PDK>,
+    {
+        Self::offset_with_seed(
+            ctx, dut, vcm, input_n, pvt, backend, None, vdd, resolution, max_iters, work_dir,
+        )
+    }
+
+    /// Like [`offset`](Self::offset), but seeds the PDK's statistical
+    /// mismatch model with `mc_seed` (when `Some`) for use as a single draw
+    /// in a [`monte_carlo_offset`](Self::monte_carlo_offset) sweep.
+    #[allow(clippy::too_many_arguments)]
+    pub fn offset_with_seed<PDK>(
+        ctx: &substrate::context::PdkContext<PDK>,
+        dut: Dut<T>,
+        vcm: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+        mc_seed: Option<u64>,
+        vdd: Decimal,
+        resolution: Decimal,
+        max_iters: usize,
+        work_dir: impl AsRef<std::path::Path>,
+    ) -> substrate::error::Result<OffsetResult>
+    where
+        PDK: Pdk + Schema + InstallCorner<C>,
+        T: StrongArmImpl<PDK>,
+    {
+        let work_dir = work_dir.as_ref();
+
+        let probe = |vdiff: Decimal, idx: usize| -> substrate::error::Result<ComparatorDecision> {
+            // Keep the probe within the legal rail limits enforced elsewhere
+            // in this crate's sweeps: vinp/vinn must stay in [0, vdd].
+            let vinp = (vcm + vdiff / dec!(2)).clamp(dec!(0), vdd);
+            let vinn = (vcm - vdiff / dec!(2)).clamp(dec!(0), vdd);
+            let mut tb = Self::with_dut(dut.clone(), vinp, vinn, input_n, pvt.clone(), backend);
+            if let Some(seed) = mc_seed {
+                tb = tb.with_mc_seed(seed);
+            }
+            Ok(ctx.simulate(tb, work_dir.join(format!("probe_{idx}")))?.decision)
+        };
+
+        let mut bracket = OffsetBracket {
+            lo: -vdd / dec!(2),
+            hi: vdd / dec!(2),
+        };
+
+        // The lower end of the bracket must produce `Neg` and the upper end
+        // `Pos`; abort the whole measurement rather than guessing if either
+        // fails to rail.
+        if probe(bracket.lo, 0)? != ComparatorDecision::Neg {
+            return Ok(None);
+        }
+        if probe(bracket.hi, 1)? != ComparatorDecision::Pos {
+            return Ok(None);
+        }
+
+        for i in 0..max_iters {
+            if bracket.hi - bracket.lo <= resolution {
+                break;
+            }
+            let mid = (bracket.lo + bracket.hi) / dec!(2);
+            match probe(mid, i + 2)? {
+                ComparatorDecision::Neg => bracket.lo = mid,
+                ComparatorDecision::Pos => bracket.hi = mid,
+                // The comparator failed to rail at this probe; abort rather
+                // than treating the non-decision as evidence for either side.
+                ComparatorDecision::Metastable => return Ok(None),
+            }
+        }
+
+        Ok(Some((bracket.lo + bracket.hi) / dec!(2)))
+    }
+
+    /// Runs a Monte Carlo sweep of [`offset_with_seed`](Self::offset_with_seed)
+    /// over `n_samples` independent mismatch draws (seeded `0..n_samples`),
+    /// landing each sample's work directory in `mc/sample_{i}`, and returns
+    /// summary statistics over the samples that railed.
+    ///
+    /// Samples are simulated concurrently, one thread per sample, since each
+    /// draw is an independent simulation against its own `mc/sample_{i}`
+    /// work directory.
+    #[allow(clippy::too_many_arguments)]
+    pub fn monte_carlo_offset<PDK>(
+        ctx: &substrate::context::PdkContext<PDK>,
+        dut: Dut<T>,
+        vcm: Decimal,
+        input_n: bool,
+        pvt: Pvt<C>,
+        backend: SimBackend,
+        vdd: Decimal,
+        resolution: Decimal,
+        max_iters: usize,
+        n_samples: u64,
+        work_dir: impl AsRef<std::path::Path>,
+    ) -> substrate::error::Result<OffsetStatistics>
+    where
+        PDK: Pdk + Schema + InstallCorner<C> + Sync,
+        T: StrongArmImpl<PDK>,
+    {
+        let mc_dir = work_dir.as_ref().join("mc");
+
+        let results = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..n_samples)
+                .map(|seed| {
+                    let dut = dut.clone();
+                    let pvt = pvt.clone();
+                    let sample_dir = mc_dir.join(format!("sample_{seed}"));
+                    scope.spawn(move || {
+                        Self::offset_with_seed(
+                            ctx,
+                            dut,
+                            vcm,
+                            input_n,
+                            pvt,
+                            backend,
+                            Some(seed),
+                            vdd,
+                            resolution,
+                            max_iters,
+                            sample_dir,
+                        )
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().expect("offset sample thread panicked"))
+                .collect::<Vec<_>>()
+        });
+
+        let mut samples = Vec::with_capacity(n_samples as usize);
+        for result in results {
+            // A sample that fails to rail is dropped rather than aborting
+            // the whole sweep; the returned statistics are over whatever
+            // fraction of samples resolved.
+            if let Some(offset) = result? {
+                samples.push(offset);
+            }
+        }
+
+        samples.sort();
+        Ok(OffsetStatistics::from_samples(samples))
+    }
+}
+
+/// Summary statistics produced by
+/// [`monte_carlo_offset`](StrongArmTranTb::monte_carlo_offset).
+#[derive(Clone, Debug, Default)]
+pub struct OffsetStatistics {
+    /// The sample mean of the measured offsets.
+    pub mean: Decimal,
+    /// The standard deviation of the measured offsets, normalized by the
+    /// sample count `n` (not `n - 1`).
+    pub sigma: Decimal,
+    /// The measured offsets, sorted ascending, for percentile/CDF queries.
+    pub samples: Vec<Decimal>,
+}
+
+impl OffsetStatistics {
+    /// Computes mean/sigma over an already-sorted slice of samples.
+    fn from_samples(samples: Vec<Decimal>) -> Self {
+        let n = Decimal::from(samples.len());
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mean = samples.iter().sum::<Decimal>() / n;
+        let variance = samples
+            .iter()
+            .map(|s| (*s - mean) * (*s - mean))
+            .sum::<Decimal>()
+            / n;
+        let sigma = variance.sqrt().unwrap_or_default();
+
+        Self {
+            mean,
+            sigma,
+            samples,
+        }
+    }
+}
+
+#[test]
+fn decide_returns_pos_when_output_p_crosses_first() {
+    let time = [dec!(0), dec!(0.5)];
+    let output_p = [dec!(0.5), dec!(0.9)];
+    let output_n = [dec!(0.5), dec!(0.1)];
+    let result = decide(&time, &output_p, &output_n, dec!(1), dec!(0), dec!(1));
+    assert_eq!(result.decision, ComparatorDecision::Pos);
+    assert_eq!(result.regen_time, Some(dec!(0.5)));
+}
+
+#[test]
+fn decide_returns_neg_when_output_n_crosses_first() {
+    let time = [dec!(0), dec!(0.5)];
+    let output_p = [dec!(0.5), dec!(0.1)];
+    let output_n = [dec!(0.5), dec!(0.9)];
+    let result = decide(&time, &output_p, &output_n, dec!(1), dec!(0), dec!(1));
+    assert_eq!(result.decision, ComparatorDecision::Neg);
+    assert_eq!(result.regen_time, Some(dec!(0.5)));
+}
+
+#[test]
+fn decide_returns_metastable_once_the_band_outlasts_the_timeout() {
+    // Split stays inside the +-0.1 band (threshold 0.5, vdd 1) the whole
+    // window, with both outputs below threshold so neither ever "crosses",
+    // and the last sample is past the metastable_timeout of 1.0.
+    let time = [dec!(0), dec!(0.5), dec!(1.2)];
+    let output_p = [dec!(0.5), dec!(0.48), dec!(0.47)];
+    let output_n = [dec!(0.5), dec!(0.45), dec!(0.44)];
+    let result = decide(&time, &output_p, &output_n, dec!(1), dec!(0), dec!(1));
+    assert_eq!(result.decision, ComparatorDecision::Metastable);
+    assert_eq!(result.regen_time, None);
+}
+
+#[test]
+fn decide_does_not_time_out_exactly_at_the_timeout_boundary() {
+    // `t - clk_edge` equals (not exceeds) metastable_timeout at the second
+    // sample, so the `>` check in `decide` must not break there -- if it
+    // did, the later sample that actually resolves would never be seen.
+    let time = [dec!(0), dec!(1), dec!(1.5)];
+    let output_p = [dec!(0.5), dec!(0.48), dec!(0.9)];
+    let output_n = [dec!(0.5), dec!(0.45), dec!(0.1)];
+    let result = decide(&time, &output_p, &output_n, dec!(1), dec!(0), dec!(1));
+    assert_eq!(result.decision, ComparatorDecision::Pos);
+    assert_eq!(result.regen_time, Some(dec!(1.5)));
+}
+
+#[test]
+fn decide_returns_metastable_when_the_window_ends_without_resolving() {
+    // Within the band the whole window, but the window itself ends before
+    // `metastable_timeout` elapses.
+    let time = [dec!(0), dec!(0.3)];
+    let output_p = [dec!(0.5), dec!(0.48)];
+    let output_n = [dec!(0.5), dec!(0.45)];
+    let result = decide(&time, &output_p, &output_n, dec!(1), dec!(0), dec!(1));
+    assert_eq!(result.decision, ComparatorDecision::Metastable);
+    assert_eq!(result.regen_time, None);
+}
+
+#[test]
+fn regen_tau_fits_the_time_constant_from_a_doubling_split() {
+    let clk_edge = dec!(0);
+    let t0 = clk_edge + REGEN_TAU_SAMPLE_T0;
+    let t1 = clk_edge + REGEN_TAU_SAMPLE_T1;
+    let time = [t0, t1];
+    // |output_p - output_n| is 0.1 at t0 and 0.2 at t1, a 2x split growth.
+    let output_p = [dec!(0.6), dec!(0.65)];
+    let output_n = [dec!(0.5), dec!(0.45)];
+    let expected = (t1 - t0).checked_div(dec!(2).checked_ln().unwrap());
+    assert_eq!(regen_tau(&time, &output_p, &output_n, clk_edge), expected);
+}
+
+#[test]
+fn regen_tau_is_none_when_the_second_sample_is_outside_the_window() {
+    let clk_edge = dec!(0);
+    let time = [clk_edge + REGEN_TAU_SAMPLE_T0];
+    let output_p = [dec!(0.6)];
+    let output_n = [dec!(0.5)];
+    assert_eq!(regen_tau(&time, &output_p, &output_n, clk_edge), None);
+}
+
+#[test]
+fn regen_tau_is_none_when_the_split_does_not_grow() {
+    let clk_edge = dec!(0);
+    let t0 = clk_edge + REGEN_TAU_SAMPLE_T0;
+    let t1 = clk_edge + REGEN_TAU_SAMPLE_T1;
+    let time = [t0, t1];
+    let output_p = [dec!(0.6), dec!(0.55)];
+    let output_n = [dec!(0.5), dec!(0.5)];
+    assert_eq!(regen_tau(&time, &output_p, &output_n, clk_edge), None);
+}
+
+#[test]
+fn offset_statistics_from_samples_computes_mean_and_sigma() {
+    let samples = vec![dec!(1), dec!(2), dec!(3)];
+    let stats = OffsetStatistics::from_samples(samples.clone());
+    assert_eq!(stats.mean, dec!(2));
+    let expected_variance = ((dec!(1) - dec!(2)) * (dec!(1) - dec!(2))
+        + (dec!(2) - dec!(2)) * (dec!(2) - dec!(2))
+        + (dec!(3) - dec!(2)) * (dec!(3) - dec!(2)))
+        / dec!(3);
+    assert_eq!(stats.sigma, expected_variance.sqrt().unwrap());
+    assert_eq!(stats.samples, samples);
+}
+
+#[test]
+fn offset_statistics_from_samples_handles_no_samples() {
+    let stats = OffsetStatistics::from_samples(Vec::new());
+    assert_eq!(stats.mean, dec!(0));
+    assert_eq!(stats.sigma, dec!(0));
+    assert!(stats.samples.is_empty());
+}